@@ -0,0 +1,232 @@
+use std::fmt::{ Display, Formatter };
+use std::str::FromStr;
+
+use ortalib::{ Chips, Edition, Mult, PokerHand, Rank, Suit };
+use serde::Deserialize;
+
+use crate::cards::{ Enhancement, PlayingCard };
+
+/// Boss blinds which change how a round is scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub enum BossBlind {
+    /// Debuffs all Club cards
+    TheClub,
+    /// Debuffs all Spade cards
+    TheGoad,
+    /// Debuffs all Heart cards
+    TheHead,
+    /// Debuffs all Diamond cards
+    TheWindow,
+    /// Debuffs all face cards
+    ThePlant,
+    /// Debuffs cards played previously this ante
+    ThePillar,
+    /// Halves the base chips and mult of the hand
+    TheFlint,
+    /// No hand type can be repeated this round
+    TheEye,
+    /// Only one hand type can be played this round
+    TheMouth,
+    /// Must play 5 cards
+    ThePsychic,
+    /// Levels down the played hand before it scores
+    TheArm,
+    /// One joker is disabled
+    CrimsonHeart,
+    /// All cards are debuffed until a joker is sold
+    VerdantLeaf,
+}
+
+/// Every boss blind, in the order they are declared
+pub const BOSS_BLINDS: [BossBlind; 13] = [
+    BossBlind::TheClub,
+    BossBlind::TheGoad,
+    BossBlind::TheHead,
+    BossBlind::TheWindow,
+    BossBlind::ThePlant,
+    BossBlind::ThePillar,
+    BossBlind::TheFlint,
+    BossBlind::TheEye,
+    BossBlind::TheMouth,
+    BossBlind::ThePsychic,
+    BossBlind::TheArm,
+    BossBlind::CrimsonHeart,
+    BossBlind::VerdantLeaf,
+];
+
+/// Why a played hand is not allowed by the boss blind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlindViolation {
+    /// The Eye, the hand type was already played this round
+    RepeatedHand(PokerHand),
+    /// The Mouth, a different hand type was already played this round
+    DifferentHand {
+        played: PokerHand,
+        allowed: PokerHand,
+    },
+    /// The Psychic, fewer than 5 cards were played
+    TooFewCards(usize),
+}
+
+impl BossBlind {
+    /// The suit debuffed by this blind, if any
+    pub fn debuffed_suit(&self) -> Option<Suit> {
+        match self {
+            BossBlind::TheClub => Some(Suit::Clubs),
+            BossBlind::TheGoad => Some(Suit::Spades),
+            BossBlind::TheHead => Some(Suit::Hearts),
+            BossBlind::TheWindow => Some(Suit::Diamonds),
+            _ => None,
+        }
+    }
+
+    /// Whether this blind debuffs a card with the given suit and enhancement.
+    /// Wild cards count as every suit, Stone cards as none, and Smeared Joker merges
+    /// suits of the same colour.
+    /// The Pillar is not handled here as it depends on the cards played before.
+    pub fn debuffs(
+        &self,
+        suit: Suit,
+        enhancement: Option<Enhancement>,
+        is_face: bool,
+        is_smeared: bool
+    ) -> bool {
+        if let Some(debuffed_suit) = self.debuffed_suit() {
+            // Stone cards have no suit
+            if enhancement == Some(Enhancement::Stone) {
+                return false;
+            }
+            return enhancement == Some(Enhancement::Wild) ||
+                suit == debuffed_suit ||
+                (is_smeared && suit.color() == debuffed_suit.color());
+        }
+
+        match self {
+            BossBlind::ThePlant => is_face,
+            _ => false,
+        }
+    }
+
+    /// Checks the played hand against blinds which restrict what can be played
+    pub fn check_hand(
+        &self,
+        poker_hand: PokerHand,
+        cards_played: usize,
+        hands_played_this_round: &[PokerHand]
+    ) -> Option<BlindViolation> {
+        match self {
+            BossBlind::TheEye if hands_played_this_round.contains(&poker_hand) => {
+                Some(BlindViolation::RepeatedHand(poker_hand))
+            }
+            BossBlind::TheMouth => {
+                hands_played_this_round
+                    .first()
+                    .filter(|&&allowed| allowed != poker_hand)
+                    .map(|&allowed| BlindViolation::DifferentHand { played: poker_hand, allowed })
+            }
+            BossBlind::ThePsychic if cards_played < 5 => {
+                Some(BlindViolation::TooFewCards(cards_played))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Halves base chips and mult for The Flint, rounding to the nearest whole number
+pub fn halve_hand_value(chips: Chips, mult: Mult) -> (Chips, Mult) {
+    ((chips * 0.5 + 0.5).floor().max(0.0), (mult * 0.5 + 0.5).floor().max(1.0))
+}
+
+/// Whether a card matches one played previously this ante, for The Pillar.
+/// Cards are matched on rank, suit, enhancement and edition.
+pub fn was_previously_played(
+    previously_played: &[PlayingCard],
+    rank: Rank,
+    suit: Suit,
+    enhancement: Option<Enhancement>,
+    edition: Option<Edition>
+) -> bool {
+    previously_played
+        .iter()
+        .any(|card| {
+            card.rank == rank &&
+                card.suit == suit &&
+                card.enhancement == enhancement &&
+                card.edition == edition
+        })
+}
+
+impl Display for BossBlind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use BossBlind::*;
+
+        #[rustfmt::skip]
+        let name = match self {
+            TheClub =>      "The Club",
+            TheGoad =>      "The Goad",
+            TheHead =>      "The Head",
+            TheWindow =>    "The Window",
+            ThePlant =>     "The Plant",
+            ThePillar =>    "The Pillar",
+            TheFlint =>     "The Flint",
+            TheEye =>       "The Eye",
+            TheMouth =>     "The Mouth",
+            ThePsychic =>   "The Psychic",
+            TheArm =>       "The Arm",
+            CrimsonHeart => "Crimson Heart",
+            VerdantLeaf =>  "Verdant Leaf",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for BossBlind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use BossBlind::*;
+
+        #[rustfmt::skip]
+        let value = match s {
+            "The Club" =>      TheClub,
+            "The Goad" =>      TheGoad,
+            "The Head" =>      TheHead,
+            "The Window" =>    TheWindow,
+            "The Plant" =>     ThePlant,
+            "The Pillar" =>    ThePillar,
+            "The Flint" =>     TheFlint,
+            "The Eye" =>       TheEye,
+            "The Mouth" =>     TheMouth,
+            "The Psychic" =>   ThePsychic,
+            "The Arm" =>       TheArm,
+            "Crimson Heart" => CrimsonHeart,
+            "Verdant Leaf" =>  VerdantLeaf,
+            _ => return Err(format!("Invalid BossBlind: `{s}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+impl TryFrom<String> for BossBlind {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for BlindViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlindViolation::RepeatedHand(hand) =>
+                write!(f, "The Eye: {} was already played this round", hand),
+            BlindViolation::DifferentHand { played, allowed } =>
+                write!(f, "The Mouth: only {} can be played this round, not {}", allowed, played),
+            BlindViolation::TooFewCards(count) =>
+                write!(f, "The Psychic: must play 5 cards, {} were played", count),
+        }
+    }
+}
@@ -0,0 +1,553 @@
+use std::fmt::{ Display, Formatter };
+use std::str::FromStr;
+
+use ortalib::{ Card, Edition, Rank, Suit };
+use serde::Deserialize;
+
+use crate::jokers::custom_joker;
+
+/// Card enhancements, a superset of `ortalib::Enhancement`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Enhancement {
+    Bonus,
+    Mult,
+    Wild,
+    Glass,
+    Steel,
+    /// Always scores with +50 chips, has no rank or suit
+    Stone,
+    /// Earns $3 when held in hand at end of round
+    Gold,
+    /// 1 in 5 chance of +20 mult and 1 in 15 chance of $20 when scored
+    Lucky,
+}
+
+/// Seals which can be attached to a playing card
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Seal {
+    /// Retriggers the card
+    Red,
+    /// Earns $3 when the card is played and scores
+    Gold,
+    /// Creates the planet of the final hand played if held in hand at end of round
+    Blue,
+    /// Creates a tarot card when discarded
+    Purple,
+}
+
+/// Playing card as written in the round input, `ortalib::Card` with an optional seal.
+/// Parsed from strings such as `K♥ Glass Foil Red Seal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct PlayingCard {
+    pub rank: Rank,
+    pub suit: Suit,
+    pub enhancement: Option<Enhancement>,
+    pub edition: Option<Edition>,
+    pub seal: Option<Seal>,
+}
+
+impl PlayingCard {
+    /// Plain card without enhancement, edition or seal
+    pub fn new(rank: Rank, suit: Suit) -> PlayingCard {
+        PlayingCard { rank, suit, enhancement: None, edition: None, seal: None }
+    }
+
+    /// Stone cards have no rank or suit
+    pub fn is_stone(&self) -> bool {
+        self.enhancement == Some(Enhancement::Stone)
+    }
+
+    pub fn is_face(&self) -> bool {
+        !self.is_stone() && self.rank.is_face()
+    }
+}
+
+impl From<&Card> for PlayingCard {
+    fn from(card: &Card) -> Self {
+        PlayingCard {
+            rank: card.rank,
+            suit: card.suit,
+            enhancement: card.enhancement.map(Enhancement::from),
+            edition: card.edition,
+            seal: None,
+        }
+    }
+}
+
+impl From<ortalib::Enhancement> for Enhancement {
+    fn from(enhancement: ortalib::Enhancement) -> Self {
+        match enhancement {
+            ortalib::Enhancement::Bonus => Enhancement::Bonus,
+            ortalib::Enhancement::Mult => Enhancement::Mult,
+            ortalib::Enhancement::Wild => Enhancement::Wild,
+            ortalib::Enhancement::Glass => Enhancement::Glass,
+            ortalib::Enhancement::Steel => Enhancement::Steel,
+        }
+    }
+}
+
+impl Display for Enhancement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use Enhancement::*;
+
+        #[rustfmt::skip]
+        let name = match self {
+            Bonus => "Bonus",
+            Mult =>  "Mult",
+            Wild =>  "Wild",
+            Glass => "Glass",
+            Steel => "Steel",
+            Stone => "Stone",
+            Gold =>  "Gold",
+            Lucky => "Lucky",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Enhancement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Enhancement::*;
+
+        #[rustfmt::skip]
+        let value = match s {
+            "Bonus" => Bonus,
+            "Mult" =>  Mult,
+            "Wild" =>  Wild,
+            "Glass" => Glass,
+            "Steel" => Steel,
+            "Stone" => Stone,
+            "Gold" =>  Gold,
+            "Lucky" => Lucky,
+            _ => return Err(format!("Invalid Enhancement: `{s}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Display for Seal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Seal::Red => write!(f, "Red Seal"),
+            Seal::Gold => write!(f, "Gold Seal"),
+            Seal::Blue => write!(f, "Blue Seal"),
+            Seal::Purple => write!(f, "Purple Seal"),
+        }
+    }
+}
+
+impl FromStr for Seal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_suffix(" Seal").unwrap_or(s) {
+            "Red" => Ok(Seal::Red),
+            "Gold" => Ok(Seal::Gold),
+            "Blue" => Ok(Seal::Blue),
+            "Purple" => Ok(Seal::Purple),
+            _ => Err(format!("Invalid Seal: `{s}`")),
+        }
+    }
+}
+
+impl Display for PlayingCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)?;
+
+        if let Some(enhancement) = self.enhancement {
+            write!(f, " {enhancement}")?;
+        }
+
+        if let Some(edition) = self.edition {
+            write!(f, " {edition}")?;
+        }
+
+        if let Some(seal) = self.seal {
+            write!(f, " {seal}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for PlayingCard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split_ascii_whitespace().collect();
+
+        // Seals are always the last two words, such as `Red Seal`
+        let seal = if parts.len() >= 3 && parts[parts.len() - 1] == "Seal" {
+            let colour = parts[parts.len() - 2];
+            parts.truncate(parts.len() - 2);
+            Some(colour.parse().map_err(|err| format!("Card `{s}` has invalid seal: {err}"))?)
+        } else {
+            None
+        };
+
+        let Some((rank_suit, modifiers)) = parts.split_first() else {
+            return Err("Cannot parse empty string".to_string());
+        };
+        if modifiers.len() > 2 {
+            return Err(format!("Card `{s}` contains too much data: `{}`", modifiers[2]));
+        }
+
+        // Suit is always exactly 1 char
+        let mut reversed = rank_suit.chars().rev();
+        let suit_str = reversed
+            .next()
+            .ok_or_else(|| format!("Card `{s}` missing rank / suit"))?
+            .to_string();
+        let rank_str: String = reversed.rev().collect();
+
+        let rank = rank_str.parse().map_err(|err| format!("Card `{s}` has invalid rank: {err}"))?;
+        let suit = suit_str.parse().map_err(|err| format!("Card `{s}` has invalid suit: {err}"))?;
+
+        // Enhancement comes before edition when both are present
+        let mut enhancement = None;
+        let mut edition = None;
+        for modifier in modifiers {
+            if
+                enhancement.is_none() &&
+                edition.is_none() &&
+                let Ok(value) = modifier.parse()
+            {
+                enhancement = Some(value);
+            } else if
+                edition.is_none() &&
+                let Ok(value) = modifier.parse::<Edition>()
+            {
+                edition = Some(value);
+            } else {
+                return Err(format!("Card `{s}` has invalid enhancement / edition: {modifier}"));
+            }
+        }
+
+        Ok(PlayingCard { rank, suit, enhancement, edition, seal })
+    }
+}
+
+impl TryFrom<String> for PlayingCard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Jokers, a superset of `ortalib::Joker`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Joker {
+    Joker,
+    JollyJoker,
+    ZanyJoker,
+    MadJoker,
+    CrazyJoker,
+    DrollJoker,
+    SlyJoker,
+    WilyJoker,
+    CleverJoker,
+    DeviousJoker,
+    CraftyJoker,
+    AbstractJoker,
+    RaisedFist,
+    Blackboard,
+    Baron,
+    GreedyJoker,
+    LustyJoker,
+    WrathfulJoker,
+    GluttonousJoker,
+    Fibonacci,
+    ScaryFace,
+    EvenSteven,
+    OddTodd,
+    Photograph,
+    SmileyFace,
+    FlowerPot,
+    FourFingers,
+    Shortcut,
+    Mime,
+    Pareidolia,
+    Splash,
+    SockAndBuskin,
+    SmearedJoker,
+    Blueprint,
+    /// +0 to +23 mult, rolled each hand
+    Misprint,
+    /// 1 in 2 chance of x1.5 mult for each scored Heart
+    Bloodstone,
+    /// 1 in 4 chance to level up the played poker hand before it scores
+    SpaceJoker,
+    /// Scaling jokers, their built up chips and mult are kept in `JokerStates`
+    RideTheBus,
+    GreenJoker,
+    IceCream,
+    Runner,
+    SquareJoker,
+    SpareTrousers,
+    Hiker,
+    WeeJoker,
+    /// Jokers which depend on the hands played earlier, see `RunHistory`
+    Supernova,
+    CardSharp,
+    Obelisk,
+    LoyaltyCard,
+    /// Jokers which depend on the resources of the round, see `RoundContext`
+    Banner,
+    MysticSummit,
+    HalfJoker,
+    Acrobat,
+    Dusk,
+    /// Joker declared in a definitions file, see `custom_jokers`
+    Custom(&'static str),
+}
+
+/// Joker as written in the round input, parsed from strings such as `Misprint Foil`
+/// or `Baron Negative`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct JokerCard {
+    pub joker: Joker,
+    pub edition: Option<Edition>,
+    /// Negative jokers take no joker slot, `ortalib::Edition` has no Negative edition
+    pub negative: bool,
+}
+
+impl JokerCard {
+    pub fn new(joker: Joker, edition: Option<Edition>) -> JokerCard {
+        JokerCard { joker, edition, negative: false }
+    }
+}
+
+impl From<&ortalib::JokerCard> for JokerCard {
+    fn from(joker_card: &ortalib::JokerCard) -> Self {
+        JokerCard::new(Joker::from(joker_card.joker), joker_card.edition)
+    }
+}
+
+impl From<ortalib::Joker> for Joker {
+    fn from(joker: ortalib::Joker) -> Self {
+        use ortalib::Joker as Base;
+
+        match joker {
+            Base::Joker => Joker::Joker,
+            Base::JollyJoker => Joker::JollyJoker,
+            Base::ZanyJoker => Joker::ZanyJoker,
+            Base::MadJoker => Joker::MadJoker,
+            Base::CrazyJoker => Joker::CrazyJoker,
+            Base::DrollJoker => Joker::DrollJoker,
+            Base::SlyJoker => Joker::SlyJoker,
+            Base::WilyJoker => Joker::WilyJoker,
+            Base::CleverJoker => Joker::CleverJoker,
+            Base::DeviousJoker => Joker::DeviousJoker,
+            Base::CraftyJoker => Joker::CraftyJoker,
+            Base::AbstractJoker => Joker::AbstractJoker,
+            Base::RaisedFist => Joker::RaisedFist,
+            Base::Blackboard => Joker::Blackboard,
+            Base::Baron => Joker::Baron,
+            Base::GreedyJoker => Joker::GreedyJoker,
+            Base::LustyJoker => Joker::LustyJoker,
+            Base::WrathfulJoker => Joker::WrathfulJoker,
+            Base::GluttonousJoker => Joker::GluttonousJoker,
+            Base::Fibonacci => Joker::Fibonacci,
+            Base::ScaryFace => Joker::ScaryFace,
+            Base::EvenSteven => Joker::EvenSteven,
+            Base::OddTodd => Joker::OddTodd,
+            Base::Photograph => Joker::Photograph,
+            Base::SmileyFace => Joker::SmileyFace,
+            Base::FlowerPot => Joker::FlowerPot,
+            Base::FourFingers => Joker::FourFingers,
+            Base::Shortcut => Joker::Shortcut,
+            Base::Mime => Joker::Mime,
+            Base::Pareidolia => Joker::Pareidolia,
+            Base::Splash => Joker::Splash,
+            Base::SockAndBuskin => Joker::SockAndBuskin,
+            Base::SmearedJoker => Joker::SmearedJoker,
+            Base::Blueprint => Joker::Blueprint,
+        }
+    }
+}
+
+impl Display for Joker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use crate::cards::Joker::*;
+
+        #[rustfmt::skip]
+        let name = match self {
+            Joker =>           "Joker",
+            JollyJoker =>      "Jolly Joker",
+            ZanyJoker =>       "Zany Joker",
+            MadJoker =>        "Mad Joker",
+            CrazyJoker =>      "Crazy Joker",
+            DrollJoker =>      "Droll Joker",
+            SlyJoker =>        "Sly Joker",
+            WilyJoker =>       "Wily Joker",
+            CleverJoker =>     "Clever Joker",
+            DeviousJoker =>    "Devious Joker",
+            CraftyJoker =>     "Crafty Joker",
+            AbstractJoker =>   "Abstract Joker",
+            RaisedFist =>      "Raised Fist",
+            Blackboard =>      "Blackboard",
+            Baron =>           "Baron",
+            GreedyJoker =>     "Greedy Joker",
+            LustyJoker =>      "Lusty Joker",
+            WrathfulJoker =>   "Wrathful Joker",
+            GluttonousJoker => "Gluttonous Joker",
+            Fibonacci =>       "Fibonacci",
+            ScaryFace =>       "Scary Face",
+            EvenSteven =>      "Even Steven",
+            OddTodd =>         "Odd Todd",
+            Photograph =>      "Photograph",
+            SmileyFace =>      "Smiley Face",
+            FlowerPot =>       "Flower Pot",
+            FourFingers =>     "Four Fingers",
+            Shortcut =>        "Shortcut",
+            Mime =>            "Mime",
+            Pareidolia =>      "Pareidolia",
+            Splash =>          "Splash",
+            SockAndBuskin =>   "Sock And Buskin",
+            SmearedJoker =>    "Smeared Joker",
+            Blueprint =>       "Blueprint",
+            Misprint =>        "Misprint",
+            Bloodstone =>      "Bloodstone",
+            SpaceJoker =>      "Space Joker",
+            RideTheBus =>      "Ride The Bus",
+            GreenJoker =>      "Green Joker",
+            IceCream =>        "Ice Cream",
+            Runner =>          "Runner",
+            SquareJoker =>     "Square Joker",
+            SpareTrousers =>   "Spare Trousers",
+            Hiker =>           "Hiker",
+            WeeJoker =>        "Wee Joker",
+            Supernova =>       "Supernova",
+            CardSharp =>       "Card Sharp",
+            Obelisk =>         "Obelisk",
+            LoyaltyCard =>     "Loyalty Card",
+            Banner =>          "Banner",
+            MysticSummit =>    "Mystic Summit",
+            HalfJoker =>       "Half Joker",
+            Acrobat =>         "Acrobat",
+            Dusk =>            "Dusk",
+            Custom(name) =>    name,
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Joker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use crate::cards::Joker::*;
+
+        #[rustfmt::skip]
+        let value = match s {
+            "Joker" =>            Joker,
+            "Jolly Joker" =>      JollyJoker,
+            "Zany Joker" =>       ZanyJoker,
+            "Mad Joker" =>        MadJoker,
+            "Crazy Joker" =>      CrazyJoker,
+            "Droll Joker" =>      DrollJoker,
+            "Sly Joker" =>        SlyJoker,
+            "Wily Joker" =>       WilyJoker,
+            "Clever Joker" =>     CleverJoker,
+            "Devious Joker" =>    DeviousJoker,
+            "Crafty Joker" =>     CraftyJoker,
+            "Abstract Joker" =>   AbstractJoker,
+            "Raised Fist" =>      RaisedFist,
+            "Blackboard" =>       Blackboard,
+            "Baron" =>            Baron,
+            "Greedy Joker" =>     GreedyJoker,
+            "Lusty Joker" =>      LustyJoker,
+            "Wrathful Joker" =>   WrathfulJoker,
+            "Gluttonous Joker" => GluttonousJoker,
+            "Fibonacci" =>        Fibonacci,
+            "Scary Face" =>       ScaryFace,
+            "Even Steven" =>      EvenSteven,
+            "Odd Todd" =>         OddTodd,
+            "Photograph" =>       Photograph,
+            "Smiley Face" =>      SmileyFace,
+            "Flower Pot" =>       FlowerPot,
+            "Four Fingers" =>     FourFingers,
+            "Shortcut" =>         Shortcut,
+            "Mime" =>             Mime,
+            "Pareidolia" =>       Pareidolia,
+            "Splash" =>           Splash,
+            "Sock And Buskin" =>  SockAndBuskin,
+            "Smeared Joker" =>    SmearedJoker,
+            "Blueprint" =>        Blueprint,
+            "Misprint" =>         Misprint,
+            "Bloodstone" =>       Bloodstone,
+            "Space Joker" =>      SpaceJoker,
+            "Ride The Bus" =>     RideTheBus,
+            "Green Joker" =>      GreenJoker,
+            "Ice Cream" =>        IceCream,
+            "Runner" =>           Runner,
+            "Square Joker" =>     SquareJoker,
+            "Spare Trousers" =>   SpareTrousers,
+            "Hiker" =>            Hiker,
+            "Wee Joker" =>        WeeJoker,
+            "Supernova" =>        Supernova,
+            "Card Sharp" =>       CardSharp,
+            "Obelisk" =>          Obelisk,
+            "Loyalty Card" =>     LoyaltyCard,
+            "Banner" =>           Banner,
+            "Mystic Summit" =>    MysticSummit,
+            "Half Joker" =>       HalfJoker,
+            "Acrobat" =>          Acrobat,
+            "Dusk" =>             Dusk,
+            _ => return custom_joker(s).ok_or_else(|| format!("Invalid Joker: `{s}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Display for JokerCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.joker)?;
+
+        if let Some(edition) = self.edition {
+            write!(f, " {edition}")?;
+        }
+        if self.negative {
+            write!(f, " Negative")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for JokerCard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (joker_str, negative) = match s.trim().rsplit_once(' ') {
+            Some((rest, "Negative")) => (rest, true),
+            _ => (s.trim(), false),
+        };
+        let (joker_str, edition) = match joker_str.rsplit_once(' ') {
+            Some((rest, last)) if let Ok(edition) = last.parse::<Edition>() => {
+                (rest, Some(edition))
+            }
+            _ => (joker_str, None),
+        };
+
+        let joker = joker_str.parse().map_err(|err| format!("Invalid JokerCard `{s}`: {err}"))?;
+        Ok(JokerCard { negative, ..JokerCard::new(joker, edition) })
+    }
+}
+
+impl TryFrom<String> for JokerCard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
@@ -1,25 +1,38 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+
 use ortalib::{
-    Card,
     Chips,
     Mult,
     PokerHand,
-    JokerCard,
-    Round,
     Edition,
-    Enhancement,
     Rank,
     Suit,
-    Joker,
 };
-use crate::jokers::JokerCardCalculator;
-use crate::pokerservice::evaluate_hand;
+use crate::jokers::{
+    EvaluationRules,
+    JokerCardCalculator,
+    JokerEffect,
+    RetriggeredCard,
+    joker_effect,
+};
+use crate::jokerstates::{ JokerStates, distinct_jokers };
+use crate::pokerservice::{ collect_cards_at, evaluate_hand };
 use crate::modifiers::{ get_editions, get_played_enhancements };
+use crate::events::{ CardLabel, EditionOwner, EventKind, EventSource, ScoreEvent };
+use crate::cards::{ Enhancement, Joker, JokerCard, PlayingCard, Seal };
+use crate::blinds::{ BlindViolation, BossBlind, halve_hand_value, was_previously_played };
+use crate::consumables::Planet;
+use crate::custom_hands::{ CustomHand, HandType, detect_custom_hands };
+use crate::effects::{ CreatedConsumable, RoundEffects };
+use crate::handlevels::HandLevels;
+use crate::rng::GameRng;
+use crate::input::RoundInput;
 
 /// Trait defining scoring behaviour for On Scored Cards and On Held Cards.
 pub trait ScoringCard {
     fn get_values(
         &self,
-        explain: &bool,
         curr_chips: &Chips,
         curr_mult: &Mult,
         data: &ScoringData
@@ -27,7 +40,6 @@ pub trait ScoringCard {
 
     fn add_scores(
         &self,
-        explain: &bool,
         curr_chips: &Chips,
         curr_mult: &Mult,
         data: &ScoringData
@@ -35,7 +47,6 @@ pub trait ScoringCard {
 
     fn check_jokers(
         &self,
-        explain: &bool,
         bonus_chips: &mut Chips,
         bonus_mult: &mut Mult,
         data: &ScoringData<'_>
@@ -43,7 +54,6 @@ pub trait ScoringCard {
 
     fn retriggers(
         &self,
-        explain: &bool,
         bonus_chips: &Chips,
         bonus_mult: &Mult,
         data: &ScoringData<'_>
@@ -52,12 +62,39 @@ pub trait ScoringCard {
 
 /// Data structure storing round data
 pub struct ScoringData<'a> {
-    pub played_cards: Vec<Card>,
+    pub played_cards: Vec<PlayingCard>,
     pub scored_cards: Vec<ScoringPlayedCard>,
-    pub round: &'a Round,
+    pub round: &'a RoundInput,
     pub pokerhands: Vec<PokerHand>,
+    /// Registered custom hands the played cards make, strongest first
+    pub custom_hands: Vec<Arc<CustomHand>>,
+    /// Custom hand which outranks every poker hand in `pokerhands` and is scored instead
+    pub custom_hand: Option<Arc<CustomHand>>,
     pub hand_cards: Vec<HandCard>,
     pub joker_cards: Vec<JokerCard>,
+    /// Registered effect of each joker of the round, looked up once since every card asks
+    pub joker_effects: Vec<(Joker, Option<Arc<dyn JokerEffect>>)>,
+    /// Rules from the jokers which are not disabled, then from `joker_cards` once Blueprint
+    /// has copied its joker
+    pub rules: EvaluationRules,
+    /// Hand levels after consumables were used
+    pub hand_levels: HandLevels,
+    /// What scaling jokers have built up, updated as the hand scores
+    pub joker_states: RefCell<JokerStates>,
+    /// Chips and mult of the poker hand before any cards are scored
+    pub base_chips: Chips,
+    pub base_mult: Mult,
+    /// Index into the round jokers of the joker disabled by Crimson Heart
+    pub disabled_joker: Option<usize>,
+    /// Set when the played hand is not allowed by the boss blind
+    pub violation: Option<BlindViolation>,
+    /// Whether scoring events should be recorded
+    pub explain: bool,
+    pub events: RefCell<Vec<ScoreEvent>>,
+    /// Money and consumables earned while scoring
+    pub effects: RefCell<RoundEffects>,
+    /// Rolls for Lucky cards and other random effects
+    pub rng: RefCell<&'a mut dyn GameRng>,
 }
 
 /// Card which scored and was also played
@@ -69,6 +106,9 @@ pub struct ScoringPlayedCard {
     pub edition: Option<Edition>,
     pub scored_card_index: usize,
     pub is_face: bool,
+    /// Debuffed by the boss blind, scores nothing and triggers no jokers
+    pub debuffed: bool,
+    pub seal: Option<Seal>,
 }
 
 /// Card in hand which can score
@@ -78,17 +118,30 @@ pub struct HandCard {
     pub suit: Suit,
     pub enhancement: Option<Enhancement>,
     pub hand_card_index: usize,
+    /// Debuffed by the boss blind, scores nothing and triggers no jokers
+    pub debuffed: bool,
+    pub seal: Option<Seal>,
 }
 
 impl ScoringData<'_> {
+    /// Records a scoring event when explaining
+    pub fn record(&self, event: ScoreEvent) {
+        if self.explain {
+            self.events.borrow_mut().push(event);
+        }
+    }
+
     /// Returns final Chips and Multiplier
-    pub fn get_score(&mut self, explain: &bool) -> (Chips, Mult) {
+    pub fn get_score(&mut self) -> (Chips, Mult) {
         // Finds the strongest pokerhand and puts into scored_cards
-        let (pokerhand_chips, pokerhand_mult) = self.find_poker_hand(&explain);
+        let (pokerhand_chips, pokerhand_mult) = self.find_poker_hand();
+        if self.violation.is_some() {
+            return (0.0, 0.0);
+        }
 
         self.joker_cards = self.round.jokers.clone();
-        // Splash, Pareidolia and Blueprint jokers
-        self.set_up_post_pokerhand_jokers(explain);
+        // Pareidolia and Blueprint jokers
+        self.set_up_post_pokerhand_jokers();
 
         // Add cards in hand to data
         self.round.cards_held_in_hand
@@ -100,80 +153,285 @@ impl ScoringData<'_> {
                     suit: card.suit,
                     enhancement: card.enhancement,
                     hand_card_index: index,
+                    debuffed: false,
+                    seal: card.seal,
                 });
             });
 
+        // Boss blind debuffs, cards still counted towards the poker hand above
+        self.set_debuffed_cards();
+
+        // Ride the Bus, Green Joker and the other scaling jokers build up before scoring
+        self.update_joker_states(|effect, states, data| effect.before_scoring(states, data));
+
         // Calculate Score of Scored Cards and Held cards
         let (scoredcard_chips, scoredcard_mult) = self.add_scoring_cards(
-            &explain,
             pokerhand_chips,
             pokerhand_mult
         );
 
         // Joker editions and indepdendent Jokers
         let (final_chips, final_mult) = self.add_joker_cards(
-            &explain,
             scoredcard_chips,
             scoredcard_mult
         );
 
-        return (final_chips, final_mult);
+        // Blue and Purple Seals
+        self.add_seal_effects();
+
+        // Glass cards can shatter after scoring
+        self.break_glass_cards();
+
+        // Ice Cream melts and Hiker's cards keep their chips for the next hand
+        self.update_joker_states(|effect, states, data| effect.after_scoring(states, data));
+
+        (final_chips, final_mult)
+    }
+
+    /// Effect of a joker, from the round's lookups when it is one of the round's jokers
+    pub fn joker_effect(&self, joker: Joker) -> Option<Arc<dyn JokerEffect>> {
+        match self.joker_effects.iter().find(|(other, _)| *other == joker) {
+            Some((_, effect)) => effect.clone(),
+            None => joker_effect(joker),
+        }
+    }
+
+    /// Earns money at the end of the round
+    pub fn earn(&self, money: i64) {
+        self.effects.borrow_mut().money += money;
+    }
+
+    /// Earns money from a card held in hand at the end of the round
+    pub fn earn_at_round_end(&self, money: i64) {
+        let mut effects = self.effects.borrow_mut();
+        effects.money += money;
+        effects.round_end_money += money;
+    }
+
+    /// Returns true with probability `numerator / denominator`
+    pub fn chance(&self, numerator: u32, denominator: u32) -> bool {
+        self.rng.borrow_mut().chance(numerator, denominator)
+    }
+
+    /// Returns true with probability `numerator / denominator` for a roll which only changes
+    /// money or cards, see `GameRng::chance_unscored`
+    pub fn chance_unscored(&self, numerator: u32, denominator: u32) -> bool {
+        self.rng.borrow_mut().chance_unscored(numerator, denominator)
+    }
+
+    /// Returns a uniformly random value in `0..sides`
+    pub fn roll(&self, sides: u32) -> u32 {
+        self.rng.borrow_mut().roll(sides)
+    }
+
+    /// Each scored Glass card has a 1 in 4 chance to be destroyed
+    fn break_glass_cards(&self) {
+        self.scored_cards
+            .iter()
+            .filter(|card| card.enhancement == Some(Enhancement::Glass) && !card.debuffed)
+            .for_each(|card| {
+                if self.chance_unscored(1, 4) {
+                    self.effects.borrow_mut().destroyed_cards.push(card.card());
+                }
+            });
     }
 
-    fn set_up_post_pokerhand_jokers(&mut self, explain: &bool) {
-        // Splash
-        self.joker_cards
-            .clone()
+    /// Seals which create consumables, assuming this is the final hand of the round
+    fn add_seal_effects(&self) {
+        let poker_hand = self.pokerhands[0];
+        let mut effects = self.effects.borrow_mut();
+
+        self.hand_cards
             .iter()
-            .for_each(|joker| {
-                joker.set_splash(self);
+            .filter(|card| card.seal == Some(Seal::Blue) && !card.debuffed)
+            .for_each(|_| {
+                if let Some(planet) = Planet::for_hand(poker_hand) {
+                    effects.consumables.push(CreatedConsumable::Planet(planet));
+                    effects.round_end_consumables.push(CreatedConsumable::Planet(planet));
+                }
             });
 
-        // Pareidolia
-        self.joker_cards
-            .clone()
+        self.round.cards_discarded
             .iter()
-            .for_each(|joker| {
-                joker.set_pareidolia(self);
+            .filter(|card| card.seal == Some(Seal::Purple))
+            .for_each(|_| {
+                effects.consumables.push(CreatedConsumable::Tarot);
+                effects.discard_consumables.push(CreatedConsumable::Tarot);
             });
+    }
+
+    /// Lets each scaling joker update its state, copies of a joker share a single state
+    fn update_joker_states(&self, update: impl Fn(&dyn JokerEffect, &mut JokerStates, &Self)) {
+        let mut states = self.joker_states.take();
+        distinct_jokers(&self.joker_cards)
+            .into_iter()
+            .filter_map(|joker| self.joker_effect(joker))
+            .for_each(|effect| update(effect.as_ref(), &mut states, self));
+        self.joker_states.replace(states);
+    }
 
+    fn set_up_post_pokerhand_jokers(&mut self) {
+        // Jokers such as Pareidolia
+        self.active_jokers().iter().for_each(|joker| {
+            joker.setup(self);
+        });
+
+        self.joker_cards = self.resolved_jokers();
+        self.rules = EvaluationRules::for_jokers(&self.joker_cards, self);
+    }
+
+    /// Jokers as they score, each Blueprint replaced by the joker it copies and the joker
+    /// disabled by Crimson Heart left out
+    fn resolved_jokers(&self) -> Vec<JokerCard> {
         // Blueprint
-        let mut reversed_jokers = self.joker_cards.clone();
+        let mut reversed_jokers = self.round.jokers.clone();
         reversed_jokers.reverse();
 
+        // A Blueprint copying a disabled joker does nothing
+        let disabled_reversed = self.disabled_joker.map(|index| reversed_jokers.len() - 1 - index);
+
         if reversed_jokers.len() > 1 {
             for i in 1..reversed_jokers.len() {
-                if reversed_jokers[i].joker == Joker::Blueprint {
-                    reversed_jokers[i].joker = reversed_jokers[i - 1].clone().joker;
+                if
+                    reversed_jokers[i].joker == Joker::Blueprint &&
+                    disabled_reversed != Some(i - 1)
+                {
+                    reversed_jokers[i].joker = reversed_jokers[i - 1].joker;
                 }
             }
         }
         reversed_jokers.reverse();
-        self.joker_cards = reversed_jokers;
 
-        if *explain {
-            println!("blueprint fixed cards {:?}", self.joker_cards);
+        // Crimson Heart
+        if let Some(index) = self.disabled_joker {
+            reversed_jokers.remove(index);
+        }
+        reversed_jokers
+    }
+
+    /// Whether the played cards make the given poker hand or custom hand
+    pub fn contains_hand(&self, hand: HandType) -> bool {
+        match hand {
+            HandType::Poker(hand) => self.pokerhands.contains(&hand),
+            HandType::Custom(name) => self.custom_hands.iter().any(|custom| custom.name == name),
         }
     }
 
+    /// Rules for evaluating the played hand from the jokers which are not disabled
+    pub fn evaluation_rules(&self) -> EvaluationRules {
+        EvaluationRules::for_jokers(&self.active_jokers(), self)
+    }
+
+    /// Jokers of the round which are not disabled by the boss blind
+    pub fn active_jokers(&self) -> Vec<JokerCard> {
+        self.round.jokers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != self.disabled_joker)
+            .map(|(_, joker)| *joker)
+            .collect()
+    }
+
+    /// Marks scored and held cards debuffed by the boss blind
+    fn set_debuffed_cards(&mut self) {
+        let Some(boss_blind) = self.round.boss_blind else {
+            return;
+        };
+
+        let is_smeared = self.rules.smeared;
+        let is_pareidolia = self.rules.all_faces;
+        let previously_played = &self.round.cards_previously_played;
+        let all_debuffed = boss_blind == BossBlind::VerdantLeaf && !self.round.history.joker_sold;
+
+        self.scored_cards.iter_mut().for_each(|card| {
+            card.debuffed =
+                all_debuffed ||
+                boss_blind.debuffs(card.suit, card.enhancement, card.is_face, is_smeared) ||
+                (boss_blind == BossBlind::ThePillar &&
+                    was_previously_played(
+                        previously_played,
+                        card.rank,
+                        card.suit,
+                        card.enhancement,
+                        card.edition
+                    ));
+        });
+
+        let held_cards = &self.round.cards_held_in_hand;
+        self.hand_cards.iter_mut().for_each(|card| {
+            let is_stone = card.enhancement == Some(Enhancement::Stone);
+            let is_face = !is_stone && (is_pareidolia || card.rank.is_face());
+            card.debuffed =
+                all_debuffed ||
+                boss_blind.debuffs(card.suit, card.enhancement, is_face, is_smeared) ||
+                (boss_blind == BossBlind::ThePillar &&
+                    was_previously_played(
+                        previously_played,
+                        card.rank,
+                        card.suit,
+                        card.enhancement,
+                        held_cards[card.hand_card_index].edition
+                    ));
+        });
+    }
+
     /// Get Poker Hand
-    fn find_poker_hand(&mut self, explain: &bool) -> (Chips, Mult) {
+    fn find_poker_hand(&mut self) -> (Chips, Mult) {
         // Finds Pokerhand and manages Four Fingers, Shortcut and Smeared Joker
-        let (result, scored_cards) = evaluate_hand(&self.played_cards, &self);
+        let (result, scored_cards) = evaluate_hand(&self.played_cards, self);
 
         self.scored_cards = scored_cards;
-        let poker_hand = result.get(0).unwrap().clone();
+        let poker_hand = *result.first().unwrap();
         self.pokerhands = result;
-        if *explain {
-            println!("{:?} {:?}", poker_hand, poker_hand.hand_value());
+        self.custom_hands = detect_custom_hands(&self.played_cards);
+        self.custom_hand = self.custom_hands
+            .first()
+            .filter(|hand| hand.above >= poker_hand)
+            .cloned();
+
+        if let Some(boss_blind) = self.round.boss_blind {
+            self.violation = boss_blind.check_hand(
+                poker_hand,
+                self.played_cards.len(),
+                &self.round.history.hands_played_this_round
+            );
+
+            // The Arm levels down the hand before it scores
+            if boss_blind == BossBlind::TheArm && self.custom_hand.is_none() {
+                let level = self.hand_levels.level(poker_hand);
+                self.hand_levels.set_level(poker_hand, level - 1);
+            }
         }
-        return poker_hand.hand_value();
+
+        let (mut chips, mut mult) = if let Some(hand) = self.custom_hand.clone() {
+            // Custom hands have no level, they always score from their own chips and mult
+            if !self.rules.every_card_scores {
+                let positions = hand.scoring_cards(&self.played_cards).unwrap_or_default();
+                self.scored_cards = collect_cards_at(&self.played_cards, &positions);
+            }
+            (hand.chips, hand.mult)
+        } else {
+            // Jokers such as Space Joker level up the hand before it scores, each copy rolls
+            let mut hand_levels = std::mem::take(&mut self.hand_levels);
+            self.resolved_jokers()
+                .iter()
+                .filter_map(|joker| self.joker_effect(joker.joker))
+                .for_each(|effect| effect.modify_hand_levels(poker_hand, &mut hand_levels, self));
+            self.hand_levels = hand_levels;
+            self.hand_levels.hand_value(poker_hand)
+        };
+        if self.round.boss_blind == Some(BossBlind::TheFlint) {
+            (chips, mult) = halve_hand_value(chips, mult);
+        }
+
+        self.base_chips = chips;
+        self.base_mult = mult;
+        (chips, mult)
     }
 
     /// Adds chips and mults from played and held cards including their jokers
     fn add_scoring_cards(
         &self,
-        explain: &bool,
         pokerhand_chips: Chips,
         pokerhand_mult: Mult
     ) -> (Chips, Mult) {
@@ -182,24 +440,23 @@ impl ScoringData<'_> {
 
         // Each card is a ScoringCard and thus can implement get_values
         self.scored_cards.iter().for_each(|card| {
-            let (new_chips, new_mult) = card.get_values(&explain, &curr_chips, &curr_mult, &self);
+            let (new_chips, new_mult) = card.get_values(&curr_chips, &curr_mult, self);
             curr_chips = new_chips;
             curr_mult = new_mult;
         });
 
         self.hand_cards.iter().for_each(|card| {
-            let (card_chips, card_mult) = card.get_values(&explain, &curr_chips, &curr_mult, &self);
+            let (card_chips, card_mult) = card.get_values(&curr_chips, &curr_mult, self);
             curr_chips = card_chips;
             curr_mult = card_mult;
         });
 
-        return (curr_chips, curr_mult);
+        (curr_chips, curr_mult)
     }
 
     /// Adds independent joker card chips and mult
     fn add_joker_cards(
         &self,
-        explain: &bool,
         current_chips: Chips,
         current_mult: Mult
     ) -> (Chips, Mult) {
@@ -208,17 +465,16 @@ impl ScoringData<'_> {
 
         self.joker_cards.iter().for_each(|joker| {
             let (new_chips, new_mult) = joker.calculate_independent_cards(
-                explain,
                 &curr_chips,
                 &curr_mult,
-                &self
+                self
             );
 
             curr_chips = new_chips;
             curr_mult = new_mult;
         });
 
-        return (curr_chips, curr_mult);
+        (curr_chips, curr_mult)
     }
 }
 
@@ -226,7 +482,6 @@ impl ScoringData<'_> {
 impl ScoringCard for ScoringPlayedCard {
     fn add_scores(
         &self,
-        explain: &bool,
         curr_chips: &Chips,
         curr_mult: &Mult,
         data: &ScoringData
@@ -234,106 +489,178 @@ impl ScoringCard for ScoringPlayedCard {
         let mut bonus_chips: Chips = *curr_chips;
         let mut bonus_mult: Mult = *curr_mult;
 
-        bonus_chips += self.rank.rank_value();
-        if *explain {
-            println!(
-                "{:?}{:?} +{:?} Chips ( {:?} x {:?} )",
-                self.rank,
-                self.suit,
-                self.rank.rank_value(),
-                bonus_chips,
-                bonus_mult
+        // Stone cards have no rank, their chips come from the enhancement
+        if self.enhancement != Some(Enhancement::Stone) {
+            bonus_chips += self.rank.rank_value();
+            data.record(
+                ScoreEvent::new(
+                    EventSource::Card(self.label()),
+                    EventKind::AddChips,
+                    self.rank.rank_value(),
+                    bonus_chips,
+                    bonus_mult
+                )
             );
+
+            // Chips the card gained from Hiker in earlier hands and triggers
+            let gained_chips = data.joker_states.borrow().card_chips(&self.card());
+            if gained_chips != 0.0 {
+                bonus_chips += gained_chips;
+                data.record(
+                    ScoreEvent::new(
+                        EventSource::Card(self.label()),
+                        EventKind::AddChips,
+                        gained_chips,
+                        bonus_chips,
+                        bonus_mult
+                    )
+                );
+            }
         }
 
-        let card_title = format!("{:?}{:?}", self.rank, self.suit);
         let (enhancement_chips, enhancement_mult) = get_played_enhancements(
             self.enhancement,
-            explain,
             &bonus_chips,
             &bonus_mult,
-            &card_title
+            self.label(),
+            data
         );
 
         let (edition_chips, edition_mult) = get_editions(
             self.edition,
-            &card_title,
-            explain,
+            EditionOwner::Card(self.label()),
             &enhancement_chips,
-            &enhancement_mult
+            &enhancement_mult,
+            data
         );
         bonus_chips = edition_chips;
         bonus_mult = edition_mult;
 
-        self.check_jokers(explain, &mut bonus_chips, &mut bonus_mult, &data);
-        return (bonus_chips, bonus_mult);
+        if self.seal == Some(Seal::Gold) {
+            data.earn(3);
+        }
+
+        self.check_jokers(&mut bonus_chips, &mut bonus_mult, data);
+        (bonus_chips, bonus_mult)
     }
 
     fn get_values(
         &self,
-        explain: &bool,
         curr_chips: &Chips,
         curr_mult: &Mult,
         data: &ScoringData
     ) -> (Chips, Mult) {
-        let (new_chips, new_mult) = self.add_scores(explain, &curr_chips, &curr_mult, &data);
+        if self.debuffed {
+            data.record(
+                ScoreEvent::new(
+                    EventSource::Card(self.label()),
+                    EventKind::Debuffed,
+                    0.0,
+                    *curr_chips,
+                    *curr_mult
+                )
+            );
+            return (*curr_chips, *curr_mult);
+        }
+
+        let (new_chips, new_mult) = self.add_scores(curr_chips, curr_mult, data);
         let (retriggered_chips, retriggered_mult) = self.retriggers(
-            explain,
             &new_chips,
             &new_mult,
-            &data
+            data
         );
-        return (retriggered_chips, retriggered_mult);
+        (retriggered_chips, retriggered_mult)
     }
 
     fn retriggers(
         &self,
-        explain: &bool,
         bonus_chips: &Chips,
         bonus_mult: &Mult,
         data: &ScoringData<'_>
-        // has_retriggered: &mut bool
     ) -> (Chips, Mult) {
         let mut curr_chips: Chips = *bonus_chips;
         let mut curr_mult: Chips = *bonus_mult;
-        // Sock and buskin: Retriggers face cards
-        data.joker_cards
-            .iter()
-            .filter(|joker| joker.joker == Joker::SockAndBuskin)
-            .for_each(|_| {
-                if self.is_face {
-                    let (new_chips, new_mult) = self.add_scores(
-                        &explain,
-                        &curr_chips,
-                        &curr_mult,
-                        &data
-                    );
-                    curr_chips = new_chips;
-                    curr_mult = new_mult;
-                }
-            });
-        return (curr_chips, curr_mult);
+        // Red Seal
+        if self.seal == Some(Seal::Red) {
+            data.record(
+                ScoreEvent::new(
+                    EventSource::Seal { seal: Seal::Red, card: self.label() },
+                    EventKind::Retrigger,
+                    1.0,
+                    curr_chips,
+                    curr_mult
+                )
+            );
+            (curr_chips, curr_mult) = self.add_scores(&curr_chips, &curr_mult, data);
+        }
+
+        // Jokers such as Sock and Buskin
+        data.joker_cards.iter().for_each(|joker| {
+            for _ in 0..joker.retrigger_count(RetriggeredCard::Scored(self), data) {
+                data.record(
+                    ScoreEvent::new(
+                        EventSource::joker_on(joker.joker, self.rank, self.suit),
+                        EventKind::Retrigger,
+                        1.0,
+                        curr_chips,
+                        curr_mult
+                    )
+                );
+                (curr_chips, curr_mult) = self.add_scores(&curr_chips, &curr_mult, data);
+            }
+        });
+        (curr_chips, curr_mult)
     }
 
     fn check_jokers(
         &self,
-        explain: &bool,
         bonus_chips: &mut Chips,
         bonus_mult: &mut Mult,
         data: &ScoringData<'_>
     ) {
+        // Every played card joker depends on rank or suit, which stone cards lack
+        if self.enhancement == Some(Enhancement::Stone) {
+            return;
+        }
+
         data.joker_cards.iter().for_each(|joker| {
             let (joker_chips, joker_mult) = joker.calculate_on_played_cards(
-                explain,
                 bonus_chips,
                 bonus_mult,
-                &self,
-                self.scored_card_index,
-                &data
+                self,
+                data
             );
             *bonus_chips = joker_chips;
             *bonus_mult = joker_mult;
         });
+
+        // Hiker and Wee Joker build up on every trigger, retriggers included
+        data.update_joker_states(|effect, states, data| {
+            effect.on_scored_card_state(states, self, data);
+        });
+    }
+}
+
+impl ScoringPlayedCard {
+    pub fn label(&self) -> CardLabel {
+        CardLabel { rank: self.rank, suit: self.suit }
+    }
+
+    /// The played card as it was given in the round
+    pub fn card(&self) -> PlayingCard {
+        PlayingCard {
+            rank: self.rank,
+            suit: self.suit,
+            enhancement: self.enhancement,
+            edition: self.edition,
+            seal: self.seal,
+        }
+    }
+}
+
+impl HandCard {
+    pub fn label(&self) -> CardLabel {
+        CardLabel { rank: self.rank, suit: self.suit }
     }
 }
 
@@ -349,35 +676,44 @@ impl PartialEq for ScoringPlayedCard {
 impl ScoringCard for HandCard {
     fn get_values(
         &self,
-        explain: &bool,
         curr_chips: &Chips,
         curr_mult: &Mult,
         data: &ScoringData
     ) -> (Chips, Mult) {
-        let (new_chips, new_mult) = self.add_scores(explain, &curr_chips, &curr_mult, &data);
+        if self.debuffed {
+            data.record(
+                ScoreEvent::new(
+                    EventSource::HeldCard(self.label()),
+                    EventKind::Debuffed,
+                    0.0,
+                    *curr_chips,
+                    *curr_mult
+                )
+            );
+            return (*curr_chips, *curr_mult);
+        }
+
+        let (new_chips, new_mult) = self.add_scores(curr_chips, curr_mult, data);
         let (retriggered_chips, retriggered_mult) = self.retriggers(
-            explain,
             &new_chips,
             &new_mult,
-            &data
+            data
         );
-        return (retriggered_chips, retriggered_mult);
+        (retriggered_chips, retriggered_mult)
     }
 
     fn check_jokers(
         &self,
-        explain: &bool,
         bonus_chips: &mut Chips,
         bonus_mult: &mut Mult,
         data: &ScoringData<'_>
     ) {
         data.joker_cards.iter().for_each(|joker| {
             let (joker_chips, joker_mult) = joker.calculate_on_held_cards(
-                explain,
                 bonus_chips,
                 bonus_mult,
-                &self,
-                &data
+                self,
+                data
             );
             *bonus_chips = joker_chips;
             *bonus_mult = joker_mult;
@@ -386,56 +722,68 @@ impl ScoringCard for HandCard {
 
     fn add_scores(
         &self,
-        explain: &bool,
         curr_chips: &Chips,
         curr_mult: &Mult,
         data: &ScoringData
     ) -> (Chips, Mult) {
         let mut bonus_chips: Chips = *curr_chips;
         let mut bonus_mult: Mult = *curr_mult;
-        if let Some(enhancement) = self.enhancement {
-            if enhancement == Enhancement::Steel {
-                bonus_mult *= 1.5;
-                if *explain {
-                    println!(
-                        "{:?}{:?} Steel x1.5 Mult ( {:?} x {:?} )",
-                        self.rank,
-                        self.suit,
-                        bonus_chips,
-                        bonus_mult
-                    );
-                }
-            }
+        if
+            let Some(enhancement) = self.enhancement &&
+            enhancement == Enhancement::Steel
+        {
+            bonus_mult *= 1.5;
+            let source = EventSource::Enhancement { enhancement, card: self.label() };
+            data.record(ScoreEvent::new(source, EventKind::XMult, 1.5, bonus_chips, bonus_mult));
         }
 
-        self.check_jokers(explain, &mut bonus_chips, &mut bonus_mult, &data);
+        // Gold cards pay out at end of round, retriggers pay again
+        if self.enhancement == Some(Enhancement::Gold) {
+            data.earn_at_round_end(3);
+        }
 
-        return (bonus_chips, bonus_mult);
+        self.check_jokers(&mut bonus_chips, &mut bonus_mult, data);
+
+        (bonus_chips, bonus_mult)
     }
 
     fn retriggers(
         &self,
-        explain: &bool,
         bonus_chips: &Chips,
         bonus_mult: &Mult,
         data: &ScoringData<'_>
     ) -> (Chips, Mult) {
         let mut curr_chips: Chips = *bonus_chips;
         let mut curr_mult: Chips = *bonus_mult;
-        // Mime Joker
-        data.joker_cards
-            .iter()
-            .filter(|joker| joker.joker == Joker::Mime)
-            .for_each(|_| {
-                let (new_chips, new_mult) = self.add_scores(
-                    &explain,
-                    &curr_chips,
-                    &curr_mult,
-                    &data
+        // Red Seal
+        if self.seal == Some(Seal::Red) {
+            data.record(
+                ScoreEvent::new(
+                    EventSource::Seal { seal: Seal::Red, card: self.label() },
+                    EventKind::Retrigger,
+                    1.0,
+                    curr_chips,
+                    curr_mult
+                )
+            );
+            (curr_chips, curr_mult) = self.add_scores(&curr_chips, &curr_mult, data);
+        }
+
+        // Jokers such as Mime
+        data.joker_cards.iter().for_each(|joker| {
+            for _ in 0..joker.retrigger_count(RetriggeredCard::Held(self), data) {
+                data.record(
+                    ScoreEvent::new(
+                        EventSource::joker_on(joker.joker, self.rank, self.suit),
+                        EventKind::Retrigger,
+                        1.0,
+                        curr_chips,
+                        curr_mult
+                    )
                 );
-                curr_chips = new_chips;
-                curr_mult = new_mult;
-            });
-        return (curr_chips, curr_mult);
+                (curr_chips, curr_mult) = self.add_scores(&curr_chips, &curr_mult, data);
+            }
+        });
+        (curr_chips, curr_mult)
     }
 }
@@ -0,0 +1,883 @@
+use std::fmt::{ Display, Formatter };
+use std::str::FromStr;
+
+use ortalib::{ Edition, PokerHand, Rank, Suit };
+use serde::Deserialize;
+
+use crate::cards::{ Enhancement, JokerCard, PlayingCard, Seal };
+use crate::handlevels::HandLevels;
+use crate::input::RoundInput;
+use crate::rng::{ GameRng, SeededRng };
+
+/// Planet cards, each levels up a single poker hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Planet {
+    Pluto,
+    Mercury,
+    Uranus,
+    Venus,
+    Saturn,
+    Jupiter,
+    Earth,
+    Mars,
+    Neptune,
+    PlanetX,
+    Ceres,
+    Eris,
+}
+
+/// Tarot cards which change the cards in hand before the hand is scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tarot {
+    /// Up to 2 cards become Lucky
+    TheMagician,
+    /// Up to 2 cards become Mult
+    TheEmpress,
+    /// Up to 2 cards become Bonus
+    TheHierophant,
+    /// 1 card becomes Wild
+    TheLovers,
+    /// 1 card becomes Steel
+    TheChariot,
+    /// 1 card becomes Glass
+    Justice,
+    /// Up to 2 cards go up one rank
+    Strength,
+    /// Destroys up to 2 cards
+    TheHangedMan,
+    /// The first of 2 cards becomes a copy of the second
+    Death,
+    /// 1 card becomes Gold
+    TheDevil,
+    /// 1 card becomes Stone
+    TheTower,
+    /// Up to 3 cards become Diamonds
+    TheStar,
+    /// Up to 3 cards become Clubs
+    TheMoon,
+    /// Up to 3 cards become Hearts
+    TheSun,
+    /// Up to 3 cards become Spades
+    TheWorld,
+}
+
+/// Spectral cards which change cards and jokers before the hand is scored.
+/// Cards they create go into the hand, cards they pick at random come from the hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Spectral {
+    /// Destroys a random card in hand, adds 3 enhanced face cards
+    Familiar,
+    /// Destroys a random card in hand, adds 2 enhanced Aces
+    Grim,
+    /// Destroys a random card in hand, adds 4 enhanced numbered cards
+    Incantation,
+    /// 1 card gets a Gold Seal
+    Talisman,
+    /// 1 card becomes Foil, Holographic or Polychrome
+    Aura,
+    /// Every card becomes the same random suit
+    Sigil,
+    /// Every card becomes the same random rank, the hand size loss is not modelled
+    Ouija,
+    /// A random joker without an edition becomes Negative
+    Ectoplasm,
+    /// Destroys 5 random cards in hand, earns $20
+    Immolate,
+    /// Copies a random joker and destroys the others
+    Ankh,
+    /// 1 card gets a Red Seal
+    DejaVu,
+    /// A random joker without an edition becomes Polychrome and the others are destroyed
+    Hex,
+    /// 1 card gets a Blue Seal
+    Trance,
+    /// 1 card gets a Purple Seal
+    Medium,
+    /// Adds 2 copies of 1 card to the hand
+    Cryptid,
+}
+
+/// Consumables used before the hand is scored, in order. Tarots and spectrals are written
+/// with the positions of the cards they target, counting `cards_played` then
+/// `cards_held_in_hand`, such as `The Empress 0 3` or `Cryptid 5`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Consumable {
+    Planet(Planet),
+    Tarot {
+        tarot: Tarot,
+        targets: Vec<usize>,
+    },
+    Spectral {
+        spectral: Spectral,
+        targets: Vec<usize>,
+    },
+}
+
+/// Consumable which changed a card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangedBy {
+    Tarot(Tarot),
+    Spectral(Spectral),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangedCard {
+    Playing(PlayingCard),
+    Joker(JokerCard),
+}
+
+/// A card changed by a consumable, `before` is `None` when the card was created and
+/// `after` is `None` when the card was destroyed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardChange {
+    pub by: ChangedBy,
+    pub before: Option<ChangedCard>,
+    pub after: Option<ChangedCard>,
+}
+
+/// The round after its tarots and spectrals were used
+#[derive(Debug, Clone)]
+pub struct UsedConsumables {
+    pub round: RoundInput,
+    pub changes: Vec<CardChange>,
+    /// Money earned by the consumables, such as Immolate
+    pub money: i64,
+}
+
+/// Cards in hand while consumables are used. Destroyed cards are left as `None` so the
+/// positions of the others stay put.
+struct Hand {
+    cards: Vec<Option<PlayingCard>>,
+    /// Number of cards at the front of `cards` which are played
+    played: usize,
+    jokers: Vec<Option<JokerCard>>,
+    money: i64,
+}
+
+impl Planet {
+    /// The poker hand this planet levels up
+    pub fn hand(&self) -> PokerHand {
+        use Planet::*;
+
+        match *self {
+            Pluto => PokerHand::HighCard,
+            Mercury => PokerHand::Pair,
+            Uranus => PokerHand::TwoPair,
+            Venus => PokerHand::ThreeOfAKind,
+            Saturn => PokerHand::Straight,
+            Jupiter => PokerHand::Flush,
+            Earth => PokerHand::FullHouse,
+            Mars => PokerHand::FourOfAKind,
+            Neptune => PokerHand::StraightFlush,
+            PlanetX => PokerHand::FiveOfAKind,
+            Ceres => PokerHand::FlushHouse,
+            Eris => PokerHand::FlushFive,
+        }
+    }
+}
+
+impl Planet {
+    /// The planet which levels up a poker hand
+    pub fn for_hand(hand: PokerHand) -> Option<Planet> {
+        use Planet::*;
+
+        [Pluto, Mercury, Uranus, Venus, Saturn, Jupiter, Earth, Mars, Neptune, PlanetX, Ceres, Eris]
+            .into_iter()
+            .find(|planet| planet.hand() == hand)
+    }
+}
+
+impl Tarot {
+    /// Most cards the tarot can target
+    pub fn max_targets(&self) -> usize {
+        use Tarot::*;
+
+        match self {
+            TheLovers | TheChariot | Justice | TheDevil | TheTower => 1,
+            TheMagician | TheEmpress | TheHierophant | Strength | TheHangedMan | Death => 2,
+            TheStar | TheMoon | TheSun | TheWorld => 3,
+        }
+    }
+
+    /// Changes the targeted cards. Destroyed cards are left as `None` so positions stay put,
+    /// targets which are out of range or already destroyed are skipped.
+    pub fn apply(&self, cards: &mut [Option<PlayingCard>], targets: &[usize]) -> Vec<CardChange> {
+        let mut changes = Vec::new();
+
+        let by = ChangedBy::Tarot(*self);
+
+        if *self == Tarot::Death {
+            let copied = targets.get(1).and_then(|&target| cards.get(target).copied().flatten());
+            let first = targets.first().and_then(|&target| cards.get_mut(target));
+            if let (Some(Some(card)), Some(copied)) = (first, copied) {
+                changes.push(CardChange::playing(by, Some(*card), Some(copied)));
+                *card = copied;
+            }
+            return changes;
+        }
+
+        for &target in targets {
+            let Some(slot) = cards.get_mut(target) else {
+                continue;
+            };
+            let Some(before) = *slot else {
+                continue;
+            };
+
+            *slot = self.change(before);
+            changes.push(CardChange::playing(by, Some(before), *slot));
+        }
+        changes
+    }
+
+    /// What a targeted card becomes
+    fn change(&self, card: PlayingCard) -> Option<PlayingCard> {
+        use Tarot::*;
+
+        let enhance = |enhancement: Enhancement| {
+            Some(PlayingCard { enhancement: Some(enhancement), ..card })
+        };
+        let convert = |suit: Suit| Some(PlayingCard { suit, ..card });
+
+        match self {
+            TheMagician => enhance(Enhancement::Lucky),
+            TheEmpress => enhance(Enhancement::Mult),
+            TheHierophant => enhance(Enhancement::Bonus),
+            TheLovers => enhance(Enhancement::Wild),
+            TheChariot => enhance(Enhancement::Steel),
+            Justice => enhance(Enhancement::Glass),
+            TheDevil => enhance(Enhancement::Gold),
+            TheTower => enhance(Enhancement::Stone),
+            Strength => Some(PlayingCard { rank: next_rank(card.rank), ..card }),
+            TheHangedMan => None,
+            Death => Some(card),
+            TheStar => convert(Suit::Diamonds),
+            TheMoon => convert(Suit::Clubs),
+            TheSun => convert(Suit::Hearts),
+            TheWorld => convert(Suit::Spades),
+        }
+    }
+}
+
+/// One rank higher, Aces go around to Twos
+fn next_rank(rank: Rank) -> Rank {
+    use Rank::*;
+
+    match rank {
+        Two => Three,
+        Three => Four,
+        Four => Five,
+        Five => Six,
+        Six => Seven,
+        Seven => Eight,
+        Eight => Nine,
+        Nine => Ten,
+        Ten => Jack,
+        Jack => Queen,
+        Queen => King,
+        King => Ace,
+        Ace => Two,
+    }
+}
+
+impl Spectral {
+    /// Number of cards the spectral must target
+    pub fn targets(&self) -> usize {
+        use Spectral::*;
+
+        match self {
+            Talisman | Aura | DejaVu | Trance | Medium | Cryptid => 1,
+            Familiar | Grim | Incantation | Sigil | Ouija | Ectoplasm | Immolate | Ankh | Hex => 0,
+        }
+    }
+}
+
+impl Hand {
+    fn new(round: &RoundInput) -> Hand {
+        Hand {
+            cards: round.cards_played
+                .iter()
+                .chain(round.cards_held_in_hand.iter())
+                .map(|&card| Some(card))
+                .collect(),
+            played: round.cards_played.len(),
+            jokers: round.jokers.iter().map(|&joker| Some(joker)).collect(),
+            money: 0,
+        }
+    }
+
+    /// Positions of the cards still held in hand
+    fn held(&self) -> Vec<usize> {
+        (self.played..self.cards.len()).filter(|&index| self.cards[index].is_some()).collect()
+    }
+
+    fn use_spectral(
+        &mut self,
+        spectral: Spectral,
+        targets: &[usize],
+        rng: &mut dyn GameRng
+    ) -> Vec<CardChange> {
+        use Spectral::*;
+
+        let by = ChangedBy::Spectral(spectral);
+        let target = targets.first().copied();
+        let mut changes = Vec::new();
+
+        match spectral {
+            Familiar | Grim | Incantation => {
+                changes.extend(self.destroy_held(1, by, rng));
+                let (ranks, count): (&[Rank], usize) = match spectral {
+                    Familiar => (&[Rank::Jack, Rank::Queen, Rank::King], 3),
+                    Grim => (&[Rank::Ace], 2),
+                    _ => (&NUMBERED_RANKS, 4),
+                };
+                for _ in 0..count {
+                    let card = random_enhanced_card(ranks, rng);
+                    self.cards.push(Some(card));
+                    changes.push(CardChange::playing(by, None, Some(card)));
+                }
+            }
+            Talisman | DejaVu | Trance | Medium => {
+                let seal = match spectral {
+                    Talisman => Seal::Gold,
+                    DejaVu => Seal::Red,
+                    Trance => Seal::Blue,
+                    _ => Seal::Purple,
+                };
+                changes.extend(
+                    self.change_card(target, by, |card| PlayingCard { seal: Some(seal), ..card })
+                );
+            }
+            Aura => {
+                let editions = [Edition::Foil, Edition::Holographic, Edition::Polychrome];
+                let edition = editions[rng.roll(editions.len() as u32) as usize];
+                changes.extend(
+                    self.change_card(target, by, |card| {
+                        PlayingCard { edition: Some(edition), ..card }
+                    })
+                );
+            }
+            Cryptid => {
+                let copied = target.and_then(|target| self.cards.get(target).copied().flatten());
+                for card in copied.into_iter().chain(copied) {
+                    self.cards.push(Some(card));
+                    changes.push(CardChange::playing(by, None, Some(card)));
+                }
+            }
+            Sigil => {
+                let suits = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+                let suit = suits[rng.roll(suits.len() as u32) as usize];
+                let convert = |card| PlayingCard { suit, ..card };
+                for index in 0..self.cards.len() {
+                    changes.extend(self.change_card(Some(index), by, convert));
+                }
+            }
+            Ouija => {
+                let rank = ALL_RANKS[rng.roll(ALL_RANKS.len() as u32) as usize];
+                let convert = |card| PlayingCard { rank, ..card };
+                for index in 0..self.cards.len() {
+                    changes.extend(self.change_card(Some(index), by, convert));
+                }
+            }
+            Immolate => {
+                changes.extend(self.destroy_held(5, by, rng));
+                self.money += 20;
+            }
+            Ectoplasm | Hex => {
+                let plain: Vec<usize> = (0..self.jokers.len())
+                    .filter(|&index| {
+                        self.jokers[index].is_some_and(|joker| {
+                            joker.edition.is_none() && !joker.negative
+                        })
+                    })
+                    .collect();
+                if plain.is_empty() {
+                    return changes;
+                }
+                let chosen = plain[rng.roll(plain.len() as u32) as usize];
+                let before = self.jokers[chosen];
+                let after = before.map(|joker| {
+                    match spectral {
+                        Ectoplasm => JokerCard { negative: true, ..joker },
+                        _ => JokerCard { edition: Some(Edition::Polychrome), ..joker },
+                    }
+                });
+                self.jokers[chosen] = after;
+                changes.push(CardChange::joker(by, before, after));
+                if spectral == Hex {
+                    changes.extend(self.destroy_jokers_except(chosen, by));
+                }
+            }
+            Ankh => {
+                let jokers: Vec<usize> = (0..self.jokers.len())
+                    .filter(|&index| self.jokers[index].is_some())
+                    .collect();
+                if jokers.is_empty() {
+                    return changes;
+                }
+                let chosen = jokers[rng.roll(jokers.len() as u32) as usize];
+                changes.extend(self.destroy_jokers_except(chosen, by));
+                // The copy is never Negative
+                let copy = self.jokers[chosen].map(|joker| JokerCard { negative: false, ..joker });
+                self.jokers.push(copy);
+                changes.push(CardChange::joker(by, None, copy));
+            }
+        }
+        changes
+    }
+
+    /// Changes the card at `target`, unless it is out of range, destroyed or already changed
+    fn change_card(
+        &mut self,
+        target: Option<usize>,
+        by: ChangedBy,
+        change: impl Fn(PlayingCard) -> PlayingCard
+    ) -> Option<CardChange> {
+        let slot = self.cards.get_mut(target?)?;
+        let before = (*slot)?;
+        let after = change(before);
+        if after == before {
+            return None;
+        }
+        *slot = Some(after);
+        Some(CardChange::playing(by, Some(before), Some(after)))
+    }
+
+    /// Destroys up to `count` random cards held in hand
+    fn destroy_held(
+        &mut self,
+        count: usize,
+        by: ChangedBy,
+        rng: &mut dyn GameRng
+    ) -> Vec<CardChange> {
+        let mut held = self.held();
+        let mut changes = Vec::new();
+        for _ in 0..count.min(held.len()) {
+            let index = held.remove(rng.roll(held.len() as u32) as usize);
+            changes.push(CardChange::playing(by, self.cards[index].take(), None));
+        }
+        changes
+    }
+
+    fn destroy_jokers_except(&mut self, kept: usize, by: ChangedBy) -> Vec<CardChange> {
+        self.jokers
+            .iter_mut()
+            .enumerate()
+            .filter(|(index, joker)| *index != kept && joker.is_some())
+            .map(|(_, joker)| CardChange::joker(by, joker.take(), None))
+            .collect()
+    }
+}
+
+const ALL_RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+const NUMBERED_RANKS: [Rank; 9] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+];
+
+/// A card of one of the ranks, with a random suit and an enhancement other than Stone
+fn random_enhanced_card(ranks: &[Rank], rng: &mut dyn GameRng) -> PlayingCard {
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+    let enhancements = [
+        Enhancement::Bonus,
+        Enhancement::Mult,
+        Enhancement::Wild,
+        Enhancement::Glass,
+        Enhancement::Steel,
+        Enhancement::Gold,
+        Enhancement::Lucky,
+    ];
+    let rank = ranks[rng.roll(ranks.len() as u32) as usize];
+    let suit = suits[rng.roll(suits.len() as u32) as usize];
+    let enhancement = enhancements[rng.roll(enhancements.len() as u32) as usize];
+    PlayingCard { enhancement: Some(enhancement), ..PlayingCard::new(rank, suit) }
+}
+
+impl CardChange {
+    fn playing(
+        by: ChangedBy,
+        before: Option<PlayingCard>,
+        after: Option<PlayingCard>
+    ) -> CardChange {
+        CardChange {
+            by,
+            before: before.map(ChangedCard::Playing),
+            after: after.map(ChangedCard::Playing),
+        }
+    }
+
+    fn joker(by: ChangedBy, before: Option<JokerCard>, after: Option<JokerCard>) -> CardChange {
+        CardChange {
+            by,
+            before: before.map(ChangedCard::Joker),
+            after: after.map(ChangedCard::Joker),
+        }
+    }
+}
+
+impl Consumable {
+    /// Applies the consumable to the hand levels of the round, tarots and spectrals leave
+    /// them unchanged
+    pub fn apply(&self, hand_levels: &mut HandLevels) {
+        match self {
+            Consumable::Planet(planet) => hand_levels.level_up(planet.hand()),
+            Consumable::Tarot { .. } | Consumable::Spectral { .. } => {}
+        }
+    }
+
+    fn targets(&self) -> &[usize] {
+        match self {
+            Consumable::Planet(_) => &[],
+            Consumable::Tarot { targets, .. } | Consumable::Spectral { targets, .. } => targets,
+        }
+    }
+}
+
+/// Uses the tarots and spectrals of the round, in order, on `cards_played`,
+/// `cards_held_in_hand` and `jokers`. Random choices are drawn from `rng`.
+/// Returns `None` when the round has neither.
+pub fn apply_consumables(round: &RoundInput, rng: &mut dyn GameRng) -> Option<UsedConsumables> {
+    if round.consumables.iter().all(|consumable| matches!(consumable, Consumable::Planet(_))) {
+        return None;
+    }
+
+    let mut hand = Hand::new(round);
+    let mut changes = Vec::new();
+    for consumable in &round.consumables {
+        match consumable {
+            Consumable::Planet(_) => {}
+            Consumable::Tarot { tarot, targets } => {
+                changes.extend(tarot.apply(&mut hand.cards, targets));
+            }
+            Consumable::Spectral { spectral, targets } => {
+                changes.extend(hand.use_spectral(*spectral, targets, rng));
+            }
+        }
+    }
+
+    let mut changed = round.clone();
+    changed.cards_played = hand.cards[..hand.played].iter().flatten().copied().collect();
+    changed.cards_held_in_hand = hand.cards[hand.played..].iter().flatten().copied().collect();
+    changed.jokers = hand.jokers.iter().flatten().copied().collect();
+    Some(UsedConsumables { round: changed, changes, money: hand.money })
+}
+
+/// The round after its tarots and spectrals were used, keeping only its planets, and the
+/// changes they made. For searches which rearrange the cards, since targets are positions in
+/// the cards as given.
+pub fn with_consumables_used(round: &RoundInput, seed: u64) -> (RoundInput, Vec<CardChange>) {
+    let Some(used) = apply_consumables(round, &mut SeededRng::new(seed)) else {
+        return (round.clone(), Vec::new());
+    };
+    let mut changed = used.round;
+    changed.consumables.retain(|consumable| matches!(consumable, Consumable::Planet(_)));
+    (changed, used.changes)
+}
+
+/// Fails when a tarot or spectral targets a card the round does not have
+pub fn check_consumable_targets(round: &RoundInput) -> Result<(), String> {
+    let hand_size = round.cards_played.len() + round.cards_held_in_hand.len();
+    for consumable in &round.consumables {
+        if let Some(target) = consumable.targets().iter().find(|&&target| target >= hand_size) {
+            return Err(
+                format!("{consumable} targets card {target}, but there are only {hand_size} cards")
+            );
+        }
+    }
+    Ok(())
+}
+
+impl Display for Planet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use Planet::*;
+
+        #[rustfmt::skip]
+        let name = match self {
+            Pluto =>   "Pluto",
+            Mercury => "Mercury",
+            Uranus =>  "Uranus",
+            Venus =>   "Venus",
+            Saturn =>  "Saturn",
+            Jupiter => "Jupiter",
+            Earth =>   "Earth",
+            Mars =>    "Mars",
+            Neptune => "Neptune",
+            PlanetX => "Planet X",
+            Ceres =>   "Ceres",
+            Eris =>    "Eris",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Planet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Planet::*;
+
+        #[rustfmt::skip]
+        let value = match s {
+            "Pluto" =>    Pluto,
+            "Mercury" =>  Mercury,
+            "Uranus" =>   Uranus,
+            "Venus" =>    Venus,
+            "Saturn" =>   Saturn,
+            "Jupiter" =>  Jupiter,
+            "Earth" =>    Earth,
+            "Mars" =>     Mars,
+            "Neptune" =>  Neptune,
+            "Planet X" => PlanetX,
+            "Ceres" =>    Ceres,
+            "Eris" =>     Eris,
+            _ => return Err(format!("Invalid Planet: `{s}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Display for Tarot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use Tarot::*;
+
+        #[rustfmt::skip]
+        let name = match self {
+            TheMagician =>   "The Magician",
+            TheEmpress =>    "The Empress",
+            TheHierophant => "The Hierophant",
+            TheLovers =>     "The Lovers",
+            TheChariot =>    "The Chariot",
+            Justice =>       "Justice",
+            Strength =>      "Strength",
+            TheHangedMan =>  "The Hanged Man",
+            Death =>         "Death",
+            TheDevil =>      "The Devil",
+            TheTower =>      "The Tower",
+            TheStar =>       "The Star",
+            TheMoon =>       "The Moon",
+            TheSun =>        "The Sun",
+            TheWorld =>      "The World",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Tarot {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Tarot::*;
+
+        #[rustfmt::skip]
+        let value = match s {
+            "The Magician" =>   TheMagician,
+            "The Empress" =>    TheEmpress,
+            "The Hierophant" => TheHierophant,
+            "The Lovers" =>     TheLovers,
+            "The Chariot" =>    TheChariot,
+            "Justice" =>        Justice,
+            "Strength" =>       Strength,
+            "The Hanged Man" => TheHangedMan,
+            "Death" =>          Death,
+            "The Devil" =>      TheDevil,
+            "The Tower" =>      TheTower,
+            "The Star" =>       TheStar,
+            "The Moon" =>       TheMoon,
+            "The Sun" =>        TheSun,
+            "The World" =>      TheWorld,
+            _ => return Err(format!("Invalid Tarot: `{s}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Display for Spectral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use Spectral::*;
+
+        #[rustfmt::skip]
+        let name = match self {
+            Familiar =>    "Familiar",
+            Grim =>        "Grim",
+            Incantation => "Incantation",
+            Talisman =>    "Talisman",
+            Aura =>        "Aura",
+            Sigil =>       "Sigil",
+            Ouija =>       "Ouija",
+            Ectoplasm =>   "Ectoplasm",
+            Immolate =>    "Immolate",
+            Ankh =>        "Ankh",
+            DejaVu =>      "Deja Vu",
+            Hex =>         "Hex",
+            Trance =>      "Trance",
+            Medium =>      "Medium",
+            Cryptid =>     "Cryptid",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Spectral {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Spectral::*;
+
+        #[rustfmt::skip]
+        let value = match s {
+            "Familiar" =>    Familiar,
+            "Grim" =>        Grim,
+            "Incantation" => Incantation,
+            "Talisman" =>    Talisman,
+            "Aura" =>        Aura,
+            "Sigil" =>       Sigil,
+            "Ouija" =>       Ouija,
+            "Ectoplasm" =>   Ectoplasm,
+            "Immolate" =>    Immolate,
+            "Ankh" =>        Ankh,
+            "Deja Vu" =>     DejaVu,
+            "Hex" =>         Hex,
+            "Trance" =>      Trance,
+            "Medium" =>      Medium,
+            "Cryptid" =>     Cryptid,
+            _ => return Err(format!("Invalid Spectral: `{s}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Display for ChangedBy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangedBy::Tarot(tarot) => write!(f, "{tarot}"),
+            ChangedBy::Spectral(spectral) => write!(f, "{spectral}"),
+        }
+    }
+}
+
+impl Display for ChangedCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangedCard::Playing(card) => write!(f, "{card}"),
+            ChangedCard::Joker(joker) => write!(f, "{joker}"),
+        }
+    }
+}
+
+impl CardChange {
+    /// What happened to the card, without the consumable which did it
+    pub fn outcome(&self) -> String {
+        match (self.before, self.after) {
+            (Some(before), Some(after)) => format!("{before} becomes {after}"),
+            (Some(before), None) => format!("{before} is destroyed"),
+            (None, Some(after)) => format!("{after} is created"),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl Display for CardChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.before, self.after) {
+            (None, None) => write!(f, "{}", self.by),
+            _ => write!(f, "{}: {}", self.by, self.outcome()),
+        }
+    }
+}
+
+impl Display for Consumable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Consumable::Planet(planet) => write!(f, "{planet}"),
+            Consumable::Tarot { tarot, targets } => {
+                write!(f, "{tarot}")?;
+                targets.iter().try_for_each(|target| write!(f, " {target}"))
+            }
+            Consumable::Spectral { spectral, targets } => {
+                write!(f, "{spectral}")?;
+                targets.iter().try_for_each(|target| write!(f, " {target}"))
+            }
+        }
+    }
+}
+
+impl FromStr for Consumable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(planet) = s.parse() {
+            return Ok(Consumable::Planet(planet));
+        }
+
+        // Tarots and spectrals are followed by the positions of the cards they target
+        let words: Vec<&str> = s.split_ascii_whitespace().collect();
+        let name_length = words
+            .iter()
+            .position(|word| word.parse::<usize>().is_ok())
+            .unwrap_or(words.len());
+        let name = words[..name_length].join(" ");
+        let targets = words[name_length..]
+            .iter()
+            .map(|word| word.parse::<usize>().map_err(|_| format!("Invalid target: `{word}`")))
+            .collect::<Result<Vec<usize>, String>>()?;
+
+        if let Ok(spectral) = name.parse::<Spectral>() {
+            match spectral.targets() {
+                0 if !targets.is_empty() => {
+                    return Err(format!("{spectral} does not target cards: `{s}`"));
+                }
+                1 if targets.len() != 1 => {
+                    return Err(format!("{spectral} targets exactly 1 card: `{s}`"));
+                }
+                _ => {}
+            }
+            return Ok(Consumable::Spectral { spectral, targets });
+        }
+
+        let tarot: Tarot = name.parse().map_err(|_| format!("Invalid Consumable: `{s}`"))?;
+        if targets.is_empty() || targets.len() > tarot.max_targets() {
+            return Err(format!("{tarot} targets 1 to {} cards: `{s}`", tarot.max_targets()));
+        }
+        if tarot == Tarot::Death && targets.len() != 2 {
+            return Err(format!("Death targets exactly 2 cards: `{s}`"));
+        }
+        Ok(Consumable::Tarot { tarot, targets })
+    }
+}
+
+impl TryFrom<String> for Consumable {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
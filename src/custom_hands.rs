@@ -0,0 +1,312 @@
+use std::collections::{ HashMap, HashSet };
+use std::fmt::{ Debug, Display, Formatter };
+use std::str::FromStr;
+use std::sync::{ Arc, LazyLock, RwLock };
+
+use ortalib::{ Chips, Mult, PokerHand, Rank, Suit };
+use serde::Deserialize;
+
+use crate::cards::{ Enhancement, PlayingCard };
+use crate::utils::parse_poker_hand;
+
+/// Hands declared in a definitions file, such as
+///
+/// ```yaml
+/// hands:
+///   - name: Rainbow
+///     chips: 45
+///     mult: 4
+///     above: Straight
+///     pattern:
+///       distinct_suits: 4
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomHandDefinitions {
+    pub hands: Vec<CustomHandDefinition>,
+}
+
+/// A custom hand as written in the definitions file
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomHandDefinition {
+    pub name: String,
+    pub chips: Chips,
+    pub mult: Mult,
+    /// Poker hand the custom hand ranks just above, such as `Full House`
+    pub above: String,
+    /// Written as a single entry map such as `rank_groups: [3, 3]`
+    #[serde(with = "serde_yaml::with::singleton_map")]
+    pub pattern: HandPattern,
+    #[serde(default)]
+    pub scoring: ScoringCards,
+}
+
+/// Built-in detection rules for custom hands written in a definitions file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandPattern {
+    /// Groups of cards sharing a rank of at least these sizes, `[3, 3]` is two three of a kinds
+    RankGroups(Vec<usize>),
+    /// Cards of at least this many different suits, Wild cards fill in any missing suit
+    DistinctSuits(usize),
+}
+
+/// Which played cards score when a custom hand is played, stone cards always score
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoringCards {
+    /// Only the cards which make up the hand
+    #[default]
+    Matched,
+    /// Every played card
+    All,
+}
+
+/// Detection predicate of a custom hand
+pub trait HandRule: Send + Sync {
+    /// Positions of the cards making up the hand, `None` when the played cards do not make
+    /// it. Stone cards have no rank or suit, so they should never be matched.
+    fn matched_cards(&self, cards: &[PlayingCard]) -> Option<Vec<usize>>;
+}
+
+/// Hand type registered on top of the standard poker hands
+#[derive(Clone)]
+pub struct CustomHand {
+    pub name: &'static str,
+    pub chips: Chips,
+    pub mult: Mult,
+    /// Ranks just above this poker hand and below the next stronger one. Custom hands above
+    /// the same poker hand rank in the order they were registered, first is strongest.
+    pub above: PokerHand,
+    pub rule: Arc<dyn HandRule>,
+    pub scoring: ScoringCards,
+}
+
+/// A standard poker hand or a registered custom hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandType {
+    Poker(PokerHand),
+    Custom(&'static str),
+}
+
+/// Registered custom hands, strongest first
+static HANDS: LazyLock<RwLock<Vec<Arc<CustomHand>>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+impl HandPattern {
+    /// Rejects patterns which every hand of cards would match, or which none could
+    fn check(&self, name: &str) -> Result<(), String> {
+        match self {
+            HandPattern::RankGroups(sizes) if sizes.is_empty() => {
+                Err(format!("{name} has no rank groups"))
+            }
+            HandPattern::RankGroups(sizes) if sizes.contains(&0) => {
+                Err(format!("{name} has a rank group of size 0"))
+            }
+            HandPattern::DistinctSuits(count) if !(1..=4).contains(count) => {
+                Err(format!("{name} needs 1 to 4 distinct suits, not {count}"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl HandRule for HandPattern {
+    fn matched_cards(&self, cards: &[PlayingCard]) -> Option<Vec<usize>> {
+        match self {
+            HandPattern::RankGroups(sizes) => match_rank_groups(cards, sizes),
+            HandPattern::DistinctSuits(count) => match_distinct_suits(cards, *count),
+        }
+    }
+}
+
+/// Cards of the largest rank groups when each is at least as big as the matching size
+fn match_rank_groups(cards: &[PlayingCard], sizes: &[usize]) -> Option<Vec<usize>> {
+    let mut groups: HashMap<Rank, Vec<usize>> = HashMap::new();
+    cards
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| !card.is_stone())
+        .for_each(|(index, card)| groups.entry(card.rank).or_default().push(index));
+
+    let mut groups: Vec<(Rank, Vec<usize>)> = groups.into_iter().collect();
+    groups.sort_by(|(a_rank, a), (b_rank, b)| b.len().cmp(&a.len()).then(b_rank.cmp(a_rank)));
+    let mut sizes = sizes.to_vec();
+    sizes.sort_by(|a, b| b.cmp(a));
+
+    if
+        sizes.len() > groups.len() ||
+        sizes.iter().zip(&groups).any(|(&size, (_, group))| group.len() < size)
+    {
+        return None;
+    }
+
+    let mut matched: Vec<usize> = groups
+        .into_iter()
+        .take(sizes.len())
+        .flat_map(|(_, group)| group)
+        .collect();
+    matched.sort();
+    Some(matched)
+}
+
+/// First card of each suit, then Wild cards for the suits still missing
+fn match_distinct_suits(cards: &[PlayingCard], count: usize) -> Option<Vec<usize>> {
+    let mut suits: HashSet<Suit> = HashSet::new();
+    let mut matched: Vec<usize> = Vec::new();
+    cards
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| !card.is_stone() && card.enhancement != Some(Enhancement::Wild))
+        .for_each(|(index, card)| {
+            if suits.insert(card.suit) {
+                matched.push(index);
+            }
+        });
+
+    let missing = count.saturating_sub(matched.len()).min(4 - suits.len());
+    matched.extend(
+        cards
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.enhancement == Some(Enhancement::Wild))
+            .map(|(index, _)| index)
+            .take(missing)
+    );
+    if matched.len() < count {
+        return None;
+    }
+    matched.sort();
+    Some(matched)
+}
+
+impl CustomHand {
+    pub fn new(
+        name: &str,
+        chips: Chips,
+        mult: Mult,
+        above: PokerHand,
+        rule: impl HandRule + 'static
+    ) -> CustomHand {
+        CustomHand {
+            // Custom hands live for the rest of the program, like the standard ones
+            name: Box::leak(name.to_string().into_boxed_str()),
+            chips,
+            mult,
+            above,
+            rule: Arc::new(rule),
+            scoring: ScoringCards::Matched,
+        }
+    }
+
+    /// Positions of the played cards which score, `None` when the cards do not make the hand
+    pub fn scoring_cards(&self, cards: &[PlayingCard]) -> Option<Vec<usize>> {
+        let matched = self.rule.matched_cards(cards)?;
+        Some(
+            cards
+                .iter()
+                .enumerate()
+                .filter(|(index, card)| {
+                    self.scoring == ScoringCards::All || card.is_stone() || matched.contains(index)
+                })
+                .map(|(index, _)| index)
+                .collect()
+        )
+    }
+}
+
+impl Debug for CustomHand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomHand")
+            .field("name", &self.name)
+            .field("chips", &self.chips)
+            .field("mult", &self.mult)
+            .field("above", &self.above)
+            .field("scoring", &self.scoring)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CustomHandDefinition {
+    pub fn to_hand(&self) -> Result<CustomHand, String> {
+        if self.name.trim().is_empty() {
+            return Err("Custom hand has no name".to_string());
+        }
+        let above = parse_poker_hand(&self.above)?;
+        self.pattern.check(&self.name)?;
+        let pattern = self.pattern.clone();
+        let mut hand = CustomHand::new(&self.name, self.chips, self.mult, above, pattern);
+        hand.scoring = self.scoring;
+        Ok(hand)
+    }
+}
+
+/// Registers a custom hand so played cards can make it and jokers can refer to it by name
+pub fn register_hand(hand: CustomHand) -> Result<HandType, String> {
+    if hand.name.parse::<HandType>().is_ok() {
+        return Err(format!("Hand `{}` already exists", hand.name));
+    }
+    let mut hands = HANDS.write().unwrap_or_else(|err| err.into_inner());
+    // Stable, so earlier hands stay stronger than later ones above the same poker hand
+    let position = hands.iter().position(|other| other.above < hand.above).unwrap_or(hands.len());
+    let name = hand.name;
+    hands.insert(position, Arc::new(hand));
+    Ok(HandType::Custom(name))
+}
+
+/// Registers every hand of a definitions file, returning the new hands. Registers none of
+/// them when any hand is invalid.
+pub fn register_custom_hands(definitions: &str) -> Result<Vec<HandType>, String> {
+    let definitions: CustomHandDefinitions = serde_yaml
+        ::from_str(definitions)
+        .map_err(|err| format!("Invalid custom hands: {err}"))?;
+
+    // Hands are looked up by name ignoring case, see `HandType::from_str`
+    let mut names = HashSet::new();
+    for hand in &definitions.hands {
+        if !names.insert(hand.name.trim().to_ascii_lowercase()) {
+            return Err(format!("Custom hand `{}` is declared twice", hand.name));
+        }
+        if hand.name.parse::<HandType>().is_ok() {
+            return Err(format!("Hand `{}` already exists", hand.name));
+        }
+    }
+    let hands: Vec<CustomHand> = definitions.hands
+        .iter()
+        .map(CustomHandDefinition::to_hand)
+        .collect::<Result<_, _>>()?;
+    hands.into_iter().map(register_hand).collect()
+}
+
+/// Registered custom hands the played cards make, strongest first
+pub fn detect_custom_hands(cards: &[PlayingCard]) -> Vec<Arc<CustomHand>> {
+    HANDS.read()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .filter(|hand| hand.rule.matched_cards(cards).is_some())
+        .cloned()
+        .collect()
+}
+
+impl FromStr for HandType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(hand) = parse_poker_hand(s) {
+            return Ok(HandType::Poker(hand));
+        }
+        HANDS.read()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .find(|hand| hand.name.eq_ignore_ascii_case(s.trim()))
+            .map(|hand| HandType::Custom(hand.name))
+            .ok_or_else(|| format!("Invalid PokerHand: `{s}`"))
+    }
+}
+
+impl Display for HandType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandType::Poker(hand) => write!(f, "{hand}"),
+            HandType::Custom(name) => write!(f, "{name}"),
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+
+use ortalib::{ Chips, Mult, Rank, Suit };
+use serde::Deserialize;
+
+use crate::cards::{ Enhancement, Joker };
+use crate::cardscorer::{ HandCard, ScoringData, ScoringPlayedCard };
+use crate::custom_hands::HandType;
+use crate::events::{ EventKind, EventSource, ScoreEvent };
+use crate::jokers::{ JokerEffect, RetriggeredCard, register_joker };
+
+/// Jokers declared in a definitions file, such as
+///
+/// ```yaml
+/// jokers:
+///   - name: Lucky Seven
+///     trigger: on_scored_card
+///     conditions:
+///       rank: "7"
+///     effect:
+///       mult: 7
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomJokerDefinitions {
+    pub jokers: Vec<CustomJoker>,
+}
+
+/// A joker whose effect applies at its trigger whenever all of its conditions hold
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomJoker {
+    pub name: String,
+    pub trigger: Trigger,
+    #[serde(default)]
+    pub conditions: Conditions,
+    /// Written as a single entry map such as `mult: 7`
+    #[serde(with = "serde_yaml::with::singleton_map")]
+    pub effect: Effect,
+}
+
+/// Scoring stage a custom joker takes part in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// Each time a played card scores
+    OnScoredCard,
+    /// Each time a card held in hand scores
+    OnHeldCard,
+    /// Once after every card has scored
+    Independent,
+}
+
+/// Conditions of a custom joker, those left out always hold. Card conditions are checked
+/// against the card which triggered the joker. Stone cards, played or held, never trigger
+/// custom jokers, even ones with only `hand_contains` or with `face: false`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(try_from = "ConditionsInput")]
+pub struct Conditions {
+    /// A poker hand or a custom hand registered before the joker
+    pub hand_contains: Option<HandType>,
+    pub suit: Option<Suit>,
+    pub rank: Option<Rank>,
+    pub face: Option<bool>,
+    pub enhancement: Option<Enhancement>,
+    pub held_cards_at_least: Option<usize>,
+    pub held_cards_at_most: Option<usize>,
+}
+
+/// Conditions as written in the definitions file, such as `suit: ♥` or `rank: K`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConditionsInput {
+    hand_contains: Option<String>,
+    suit: Option<String>,
+    rank: Option<String>,
+    face: Option<bool>,
+    enhancement: Option<String>,
+    held_cards_at_least: Option<usize>,
+    held_cards_at_most: Option<usize>,
+}
+
+/// What a custom joker does when it triggers
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    Chips(f64),
+    Mult(f64),
+    #[serde(rename = "xmult")]
+    XMult(f64),
+    /// Scores the triggering card again this many times
+    Retrigger(u32),
+}
+
+/// Card a custom joker's card conditions are checked against
+struct TriggeringCard {
+    rank: Rank,
+    suit: Suit,
+    enhancement: Option<Enhancement>,
+    is_face: bool,
+}
+
+/// Effect of a registered custom joker
+struct CustomJokerEffect {
+    joker: Joker,
+    trigger: Trigger,
+    conditions: Conditions,
+    effect: Effect,
+}
+
+impl TryFrom<ConditionsInput> for Conditions {
+    type Error = String;
+
+    fn try_from(input: ConditionsInput) -> Result<Self, Self::Error> {
+        Ok(Conditions {
+            hand_contains: input.hand_contains.as_deref().map(str::parse).transpose()?,
+            suit: input.suit.as_deref().map(str::parse).transpose()?,
+            rank: input.rank.as_deref().map(str::parse).transpose()?,
+            face: input.face,
+            enhancement: input.enhancement.as_deref().map(str::parse).transpose()?,
+            held_cards_at_least: input.held_cards_at_least,
+            held_cards_at_most: input.held_cards_at_most,
+        })
+    }
+}
+
+impl Conditions {
+    /// Whether the conditions check the triggering card
+    fn has_card_conditions(&self) -> bool {
+        self.suit.is_some() ||
+            self.rank.is_some() ||
+            self.face.is_some() ||
+            self.enhancement.is_some()
+    }
+
+    fn hold(&self, card: Option<TriggeringCard>, data: &ScoringData) -> bool {
+        let held_cards = data.hand_cards.len();
+        let hand_matches = self.hand_contains.is_none_or(|hand| data.contains_hand(hand));
+        let held_matches =
+            self.held_cards_at_least.is_none_or(|least| held_cards >= least) &&
+            self.held_cards_at_most.is_none_or(|most| held_cards <= most);
+        if !hand_matches || !held_matches {
+            return false;
+        }
+
+        let Some(card) = card else {
+            return !self.has_card_conditions();
+        };
+        // Wild cards are every suit
+        let suit_matches = self.suit.is_none_or(|suit| {
+            card.suit == suit ||
+                card.enhancement == Some(Enhancement::Wild) ||
+                (data.rules.smeared && card.suit.color() == suit.color())
+        });
+        suit_matches &&
+            self.rank.is_none_or(|rank| card.rank == rank) &&
+            self.face.is_none_or(|face| card.is_face == face) &&
+            self.enhancement.is_none_or(|enhancement| card.enhancement == Some(enhancement))
+    }
+}
+
+impl CustomJoker {
+    /// Fails when the joker could never trigger as written
+    fn check(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Custom joker has no name".to_string());
+        }
+        if self.name.parse::<Joker>().is_ok() {
+            return Err(format!("Joker `{}` already exists", self.name));
+        }
+        if self.trigger == Trigger::Independent {
+            if self.conditions.has_card_conditions() {
+                return Err(format!("{} is independent so it has no card to check", self.name));
+            }
+            if matches!(self.effect, Effect::Retrigger(_)) {
+                return Err(format!("{} is independent so it has no card to retrigger", self.name));
+            }
+        }
+        // Stone cards have no rank or suit, so they never trigger card jokers
+        if self.conditions.enhancement == Some(Enhancement::Stone) {
+            return Err(format!("{} checks for Stone cards, which never trigger jokers", self.name));
+        }
+        Ok(())
+    }
+
+    /// Registers the joker so rounds can use it by name
+    pub fn register(&self) -> Result<Joker, String> {
+        self.check()?;
+
+        // Custom jokers live for the rest of the program, like the built-in ones
+        let joker = Joker::Custom(Box::leak(self.name.clone().into_boxed_str()));
+        register_joker(joker, CustomJokerEffect {
+            joker,
+            trigger: self.trigger,
+            conditions: self.conditions.clone(),
+            effect: self.effect,
+        });
+        Ok(joker)
+    }
+}
+
+/// Registers every joker of a definitions file, returning the new jokers. Registers none of
+/// them when any joker is invalid.
+pub fn register_custom_jokers(definitions: &str) -> Result<Vec<Joker>, String> {
+    let definitions: CustomJokerDefinitions = serde_yaml
+        ::from_str(definitions)
+        .map_err(|err| format!("Invalid custom jokers: {err}"))?;
+
+    let mut names = HashSet::new();
+    for joker in &definitions.jokers {
+        if !names.insert(&joker.name) {
+            return Err(format!("Custom joker `{}` is declared twice", joker.name));
+        }
+        joker.check()?;
+    }
+    definitions.jokers.iter().map(CustomJoker::register).collect()
+}
+
+impl CustomJokerEffect {
+    fn apply(
+        &self,
+        source: EventSource,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let (chips, mult) = (*current_chips, *current_mult);
+        let (chips, mult, kind, delta) = match self.effect {
+            Effect::Chips(delta) => (chips + delta, mult, EventKind::AddChips, delta),
+            Effect::Mult(delta) => (chips, mult + delta, EventKind::AddMult, delta),
+            Effect::XMult(delta) => (chips, mult * delta, EventKind::XMult, delta),
+            Effect::Retrigger(_) => {
+                return (chips, mult);
+            }
+        };
+        data.record(ScoreEvent::new(source, kind, delta, chips, mult));
+        (chips, mult)
+    }
+}
+
+impl JokerEffect for CustomJokerEffect {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if self.trigger != Trigger::OnScoredCard || !self.conditions.hold(Some(card.into()), data) {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(self.joker, card.rank, card.suit);
+        self.apply(source, current_chips, current_mult, data)
+    }
+
+    fn on_held_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &HandCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let triggering = TriggeringCard::held(card, data);
+        if self.trigger != Trigger::OnHeldCard || !self.conditions.hold(Some(triggering), data) {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(self.joker, card.rank, card.suit);
+        self.apply(source, current_chips, current_mult, data)
+    }
+
+    fn retrigger_count(&self, card: RetriggeredCard, data: &ScoringData) -> u32 {
+        let Effect::Retrigger(count) = self.effect else {
+            return 0;
+        };
+        let card = match (self.trigger, card) {
+            (Trigger::OnScoredCard, RetriggeredCard::Scored(card)) => TriggeringCard::from(card),
+            (Trigger::OnHeldCard, RetriggeredCard::Held(card)) => TriggeringCard::held(card, data),
+            _ => {
+                return 0;
+            }
+        };
+        if self.conditions.hold(Some(card), data) { count } else { 0 }
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if self.trigger != Trigger::Independent || !self.conditions.hold(None, data) {
+            return (*current_chips, *current_mult);
+        }
+        self.apply(EventSource::joker(self.joker), current_chips, current_mult, data)
+    }
+}
+
+impl TriggeringCard {
+    fn held(card: &HandCard, data: &ScoringData) -> TriggeringCard {
+        let is_stone = card.enhancement == Some(Enhancement::Stone);
+        TriggeringCard {
+            rank: card.rank,
+            suit: card.suit,
+            enhancement: card.enhancement,
+            is_face: !is_stone && (data.rules.all_faces || card.rank.is_face()),
+        }
+    }
+}
+
+impl From<&ScoringPlayedCard> for TriggeringCard {
+    fn from(card: &ScoringPlayedCard) -> Self {
+        TriggeringCard {
+            rank: card.rank,
+            suit: card.suit,
+            enhancement: card.enhancement,
+            is_face: card.is_face,
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+use std::fmt::{ Display, Formatter };
+
+use ortalib::{ PokerHand, Rank, Suit };
+use serde::Serialize;
+
+use crate::cards::PlayingCard;
+use crate::discard::HandProbability;
+use crate::input::RoundInput;
+use crate::optimize::combinations;
+use crate::rng::{ GameRng, SeededRng };
+use crate::roundscorer::RoundScorer;
+use crate::utils::POKER_HANDS;
+
+/// Where every card of the deck is during the round
+#[derive(Debug, Clone)]
+pub struct Deck {
+    pub full: Vec<PlayingCard>,
+    /// Cards played this hand and earlier this ante
+    pub played: Vec<PlayingCard>,
+    pub discarded: Vec<PlayingCard>,
+    pub held: Vec<PlayingCard>,
+    pub remaining: Vec<PlayingCard>,
+}
+
+/// Remaining cards which complete a poker hand when drawn next
+#[derive(Debug, Clone, Serialize)]
+pub struct Outs {
+    pub poker_hand: String,
+    pub cards: Vec<String>,
+}
+
+/// What is left in the deck and the chance of drawing into each poker hand
+#[derive(Debug, Clone, Serialize)]
+pub struct DeckReport {
+    pub deck_size: usize,
+    pub played: usize,
+    pub discarded: usize,
+    pub held: Vec<String>,
+    pub remaining: Vec<String>,
+    pub draws: usize,
+    /// Draws scored to find the probabilities
+    pub samples: usize,
+    /// True when every possible draw was scored instead of a sample of them
+    pub exact: bool,
+    /// Probability of the held cards and the draws containing each poker hand
+    pub hand_probabilities: Vec<HandProbability>,
+    /// Poker hands the held cards do not already contain, and the cards which complete them
+    pub outs: Vec<Outs>,
+}
+
+impl Deck {
+    /// The standard 52 card deck
+    pub fn standard() -> Vec<PlayingCard> {
+        use Rank::*;
+
+        let ranks = [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace];
+        [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds]
+            .iter()
+            .flat_map(|&suit| ranks.iter().map(move |&rank| PlayingCard::new(rank, suit)))
+            .collect()
+    }
+
+    /// Tracks the round's cards against `full_deck`, or the standard deck. The remaining cards
+    /// are `deck` when given, otherwise every card of the full deck which was not seen.
+    pub fn from_round(round: &RoundInput) -> Deck {
+        let full = round.full_deck.clone().unwrap_or_else(Deck::standard);
+        let played: Vec<PlayingCard> = round.cards_played
+            .iter()
+            .chain(round.cards_previously_played.iter())
+            .copied()
+            .collect();
+        let discarded = round.cards_discarded.clone();
+        let held = round.cards_held_in_hand.clone();
+
+        let remaining = match round.deck.is_empty() {
+            false => round.deck.clone(),
+            true => {
+                let mut remaining = full.clone();
+                played
+                    .iter()
+                    .chain(discarded.iter())
+                    .chain(held.iter())
+                    .for_each(|card| remove_card(&mut remaining, card));
+                remaining
+            }
+        };
+
+        Deck { full, played, discarded, held, remaining }
+    }
+
+    /// Probability of the held cards and the next `draws` cards containing each poker hand.
+    /// Every draw is scored when there are at most `samples` of them, otherwise `samples`
+    /// seeded draws are. Returns the probabilities, the draws scored and whether it was exact.
+    pub fn draw_probabilities(
+        &self,
+        round: &RoundInput,
+        draws: usize,
+        samples: usize,
+        seed: u64
+    ) -> (Vec<HandProbability>, usize, bool) {
+        let draws = draws.min(self.remaining.len());
+        let exact = binomial(self.remaining.len(), draws) <= (samples as f64);
+        let drawn: Vec<Vec<usize>> = if exact {
+            combinations(self.remaining.len(), draws)
+        } else {
+            let mut rng = SeededRng::new(seed);
+            (0..samples).map(|_| sample_draw(&mut rng, self.remaining.len(), draws)).collect()
+        };
+
+        let mut rng = SeededRng::new(seed);
+        let mut scorer = RoundScorer::new(round, &mut rng);
+        let mut hits = [0usize; POKER_HANDS.len()];
+        for draw in &drawn {
+            let mut cards = self.held.clone();
+            cards.extend(draw.iter().map(|&index| self.remaining[index]));
+            let contained = scorer.contained_hands(&cards);
+            POKER_HANDS.iter()
+                .enumerate()
+                .filter(|(_, hand)| contained.contains(hand))
+                .for_each(|(index, _)| {
+                    hits[index] += 1;
+                });
+        }
+
+        let count = drawn.len().max(1) as f64;
+        let probabilities = POKER_HANDS.iter()
+            .zip(hits)
+            .rev()
+            .filter(|(_, hits)| *hits > 0)
+            .map(|(hand, hits)| HandProbability {
+                poker_hand: hand.to_string(),
+                probability: (hits as f64) / count,
+            })
+            .collect();
+        (probabilities, drawn.len(), exact)
+    }
+
+    /// Remaining cards which give the held cards a poker hand they do not already contain
+    pub fn outs(&self, round: &RoundInput) -> Vec<Outs> {
+        let mut rng = SeededRng::new(0);
+        let mut scorer = RoundScorer::new(round, &mut rng);
+        let already = scorer.contained_hands(&self.held);
+
+        // Duplicate cards complete the same hands, so each is only listed once
+        let mut distinct: Vec<PlayingCard> = Vec::new();
+        self.remaining.iter().for_each(|card| {
+            if !distinct.contains(card) {
+                distinct.push(*card);
+            }
+        });
+
+        let completed: Vec<(PlayingCard, HashSet<PokerHand>)> = distinct
+            .into_iter()
+            .map(|card| {
+                let mut cards = self.held.clone();
+                cards.push(card);
+                (card, scorer.contained_hands(&cards))
+            })
+            .collect();
+
+        POKER_HANDS.iter()
+            .rev()
+            .filter(|hand| !already.contains(hand))
+            .filter_map(|hand| {
+                let cards: Vec<String> = completed
+                    .iter()
+                    .filter(|(_, hands)| hands.contains(hand))
+                    .map(|(card, _)| card.to_string())
+                    .collect();
+                (!cards.is_empty()).then(|| Outs { poker_hand: hand.to_string(), cards })
+            })
+            .collect()
+    }
+
+    /// Summary of the deck along with the draw probabilities and outs of the held cards
+    pub fn report(&self, round: &RoundInput, draws: usize, samples: usize, seed: u64) -> DeckReport {
+        let (hand_probabilities, samples, exact) = self.draw_probabilities(
+            round,
+            draws,
+            samples,
+            seed
+        );
+        DeckReport {
+            deck_size: self.full.len(),
+            played: self.played.len(),
+            discarded: self.discarded.len(),
+            held: self.held
+                .iter()
+                .map(|card| card.to_string())
+                .collect(),
+            remaining: self.remaining
+                .iter()
+                .map(|card| card.to_string())
+                .collect(),
+            draws: draws.min(self.remaining.len()),
+            samples,
+            exact,
+            hand_probabilities,
+            outs: self.outs(round),
+        }
+    }
+}
+
+/// Removes a card from the pile, or a card of the same rank and suit when it was enhanced
+fn remove_card(pile: &mut Vec<PlayingCard>, card: &PlayingCard) {
+    let position = pile
+        .iter()
+        .position(|other| other == card)
+        .or_else(|| {
+            pile.iter().position(|other| other.rank == card.rank && other.suit == card.suit)
+        });
+    if let Some(position) = position {
+        pile.remove(position);
+    }
+}
+
+/// Picks `size` distinct positions of a deck uniformly at random
+pub(crate) fn sample_draw(rng: &mut dyn GameRng, deck_size: usize, size: usize) -> Vec<usize> {
+    let mut positions: Vec<usize> = (0..deck_size).collect();
+    for i in 0..size {
+        let j = i + (rng.roll((deck_size - i) as u32) as usize);
+        positions.swap(i, j);
+    }
+    positions.truncate(size);
+    positions
+}
+
+/// Number of ways to choose `k` of `n`, as a float so large decks cannot overflow
+pub(crate) fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (0..k).fold(1.0, |total, i| (total * ((n - i) as f64)) / ((i + 1) as f64))
+}
+
+impl Display for DeckReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Deck of {}: {} played, {} discarded, {} held, {} remaining",
+            self.deck_size,
+            self.played,
+            self.discarded,
+            self.held.len(),
+            self.remaining.len()
+        )?;
+        writeln!(f, "Remaining: {}", self.remaining.join(", "))?;
+
+        let draws = match self.exact {
+            true => format!("all {} draws", self.samples),
+            false => format!("{} sampled draws", self.samples),
+        };
+        writeln!(f, "Chance within the next {} cards ({}):", self.draws, draws)?;
+        for hand in &self.hand_probabilities {
+            writeln!(f, "  {:<16} {:>8.2}%", hand.poker_hand, hand.probability * 100.0)?;
+        }
+
+        writeln!(f, "Outs:")?;
+        for outs in &self.outs {
+            writeln!(f, "  {:<16} {}", outs.poker_hand, outs.cards.join(", "))?;
+        }
+        Ok(())
+    }
+}
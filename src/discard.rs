@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fmt::{ Display, Formatter };
+
+use serde::Serialize;
+
+use crate::cards::PlayingCard;
+use crate::consumables::with_consumables_used;
+use crate::deck::{ Deck, binomial, sample_draw };
+use crate::input::{ RoundContextInput, RoundInput };
+use crate::optimize::{ Play, combinations, optimize };
+use crate::rng::SeededRng;
+use crate::roundscorer::ScoreOptions;
+
+/// Most cards a single discard can remove
+const MAX_DISCARDED_CARDS: usize = 5;
+
+/// Draws tried for every discard before the most promising ones are looked at closely
+const SCREEN_DRAWS: usize = 8;
+
+/// Expected result of discarding some cards and drawing replacements from the deck
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscardCandidate {
+    pub discard: Vec<String>,
+    /// Expected score of the best play from the hand after drawing
+    pub expected_score: f64,
+    /// Probability of beating the blind target, if there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pass_probability: Option<f64>,
+    /// Probability of each poker hand being the best play, most likely first
+    pub hand_probabilities: Vec<HandProbability>,
+    pub draws: usize,
+    /// True when every possible draw was scored instead of a sample of them
+    pub exact: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HandProbability {
+    pub poker_hand: String,
+    pub probability: f64,
+}
+
+/// Discards ranked by the expected score of the next hand, best first
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscardAdvice {
+    pub deck_size: usize,
+    pub candidates_considered: usize,
+    /// Playing the hand as it is, without discarding
+    pub keep: DiscardCandidate,
+    pub candidates: Vec<DiscardCandidate>,
+}
+
+/// Recommends which of `cards_played` and `cards_held_in_hand` to discard. Each discard of 1 to 5
+/// cards is scored by drawing replacements from the remaining deck, see `Deck::from_round`, and
+/// taking the best play of the new hand.
+/// Draws are enumerated when there are at most `draws` of them and sampled otherwise, with the
+/// same seeded draws for every discard so they are compared fairly. Every discard is screened
+/// with a few draws first and only the most promising are scored with all `draws`.
+pub fn advise_discard(
+    round: &RoundInput,
+    options: &ScoreOptions,
+    top: usize,
+    draws: usize
+) -> Result<DiscardAdvice, String> {
+    if round.context().hands_left == 0 {
+        return Err("No hands remaining to play after discarding".to_string());
+    }
+
+    let options = ScoreOptions { explain: false, ..options.clone() };
+    // Drawn from the deck as given, before tarots and spectrals change any cards in hand
+    let deck = Deck::from_round(round).remaining;
+    let (round, _) = with_consumables_used(round, options.seed);
+    let round = &round;
+    let hand: Vec<PlayingCard> = round.cards_played
+        .iter()
+        .chain(round.cards_held_in_hand.iter())
+        .copied()
+        .collect();
+
+    // Screening draws are scored again for the candidates which pass, so the best play of
+    // every hand after drawing is kept
+    let mut best_plays: HashMap<Vec<PlayingCard>, Option<Play>> = HashMap::new();
+    let keep = evaluate_discard(round, &options, &hand, &deck, &[], 1, &mut best_plays);
+
+    let mut discards: Vec<Vec<usize>> = Vec::new();
+    if round.context().discards_left != 0 {
+        let most = MAX_DISCARDED_CARDS.min(hand.len()).min(deck.len());
+        for size in 1..=most {
+            discards.extend(combinations(hand.len(), size));
+        }
+    }
+
+    let mut candidates: Vec<(Vec<usize>, DiscardCandidate)> = discards
+        .into_iter()
+        .map(|discard| {
+            let candidate = evaluate_discard(
+                round,
+                &options,
+                &hand,
+                &deck,
+                &discard,
+                draws.min(SCREEN_DRAWS),
+                &mut best_plays
+            );
+            (discard, candidate)
+        })
+        .collect();
+    let candidates_considered = candidates.len();
+    sort_candidates(&mut candidates);
+
+    // Screening is noisy, so keep a margin of candidates before scoring them properly
+    candidates.truncate(top.max(1) * 2);
+    for (discard, candidate) in candidates.iter_mut() {
+        if !candidate.exact && candidate.draws < draws {
+            *candidate = evaluate_discard(
+                round,
+                &options,
+                &hand,
+                &deck,
+                discard,
+                draws,
+                &mut best_plays
+            );
+        }
+    }
+    sort_candidates(&mut candidates);
+    candidates.truncate(top);
+
+    Ok(DiscardAdvice {
+        deck_size: deck.len(),
+        candidates_considered,
+        keep,
+        candidates: candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect(),
+    })
+}
+
+fn sort_candidates(candidates: &mut [(Vec<usize>, DiscardCandidate)]) {
+    candidates.sort_by(|(_, a), (_, b)| b.expected_score.total_cmp(&a.expected_score));
+}
+
+/// Scores the best play after discarding the cards at `discard` and drawing replacements.
+/// Hands already in `best_plays` are not scored again.
+fn evaluate_discard(
+    round: &RoundInput,
+    options: &ScoreOptions,
+    hand: &[PlayingCard],
+    deck: &[PlayingCard],
+    discard: &[usize],
+    draws: usize,
+    best_plays: &mut HashMap<Vec<PlayingCard>, Option<Play>>
+) -> DiscardCandidate {
+    let kept: Vec<PlayingCard> = (0..hand.len())
+        .filter(|index| !discard.contains(index))
+        .map(|index| hand[index])
+        .collect();
+
+    // Discarding uses up a discard and lets jokers such as Green Joker react, so Banner,
+    // Mystic Summit and the scaling jokers see the round as it will be
+    let discarded: Vec<PlayingCard> = discard.iter().map(|&index| hand[index]).collect();
+    let mut discarded_round = round.clone();
+    discarded_round.cards_held_in_hand = Vec::new();
+    if !discarded.is_empty() {
+        discarded_round.context = Some(RoundContextInput {
+            discards_left: Some(round.context().discards_left.saturating_sub(1)),
+            ..round.context.unwrap_or_default()
+        });
+        discarded_round.joker_states.discard(&round.jokers, &discarded);
+        discarded_round.cards_discarded.extend(discarded.iter().copied());
+    }
+
+    let possible_draws = binomial(deck.len(), discard.len());
+    let exact = possible_draws <= (draws as f64);
+    let drawn: Vec<Vec<usize>> = if exact {
+        combinations(deck.len(), discard.len())
+    } else {
+        let mut rng = SeededRng::new(options.seed);
+        (0..draws).map(|_| sample_draw(&mut rng, deck.len(), discard.len())).collect()
+    };
+
+    let mut total_score = 0.0;
+    let mut passed: usize = 0;
+    let mut has_target = false;
+    let mut hands: HashMap<String, usize> = HashMap::new();
+    for draw in &drawn {
+        let mut new_hand = kept.clone();
+        new_hand.extend(draw.iter().map(|&index| deck[index]));
+        let best = best_plays.entry(new_hand).or_insert_with_key(|new_hand| {
+            let mut candidate = discarded_round.clone();
+            candidate.cards_played = new_hand.clone();
+            optimize(&candidate, options, 1).plays.into_iter().next()
+        });
+        let Some(best) = best else {
+            continue;
+        };
+        total_score += best.score;
+        if let Some(play_passed) = best.passed {
+            has_target = true;
+            passed += usize::from(play_passed);
+        }
+        *hands.entry(best.poker_hand.clone()).or_insert(0) += 1;
+    }
+
+    let count = drawn.len().max(1) as f64;
+    let mut hand_probabilities: Vec<HandProbability> = hands
+        .into_iter()
+        .map(|(poker_hand, hits)| HandProbability {
+            poker_hand,
+            probability: (hits as f64) / count,
+        })
+        .collect();
+    hand_probabilities.sort_by(|a, b| {
+        b.probability.total_cmp(&a.probability).then_with(|| a.poker_hand.cmp(&b.poker_hand))
+    });
+
+    DiscardCandidate {
+        discard: discarded
+            .iter()
+            .map(|card| card.to_string())
+            .collect(),
+        expected_score: total_score / count,
+        pass_probability: has_target.then(|| (passed as f64) / count),
+        hand_probabilities,
+        draws: drawn.len(),
+        exact,
+    }
+}
+
+impl Display for DiscardCandidate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let discard = match self.discard.is_empty() {
+            true => "nothing".to_string(),
+            false => self.discard.join(", "),
+        };
+        write!(f, "{:>12.2} discard {}", self.expected_score, discard)?;
+        if let Some(pass_probability) = self.pass_probability {
+            write!(f, ", beats target {:.2}%", pass_probability * 100.0)?;
+        }
+        writeln!(f)?;
+
+        let hands: Vec<String> = self.hand_probabilities
+            .iter()
+            .map(|hand| format!("{} {:.2}%", hand.poker_hand, hand.probability * 100.0))
+            .collect();
+        write!(f, "             {}", hands.join(", "))?;
+        match self.exact {
+            _ if self.discard.is_empty() => Ok(()),
+            true => write!(f, " (all {} draws)", self.draws),
+            false => write!(f, " ({} sampled draws)", self.draws),
+        }
+    }
+}
+
+impl Display for DiscardAdvice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Best of {} discards with {} cards left in the deck",
+            self.candidates_considered,
+            self.deck_size
+        )?;
+        for (rank, candidate) in self.candidates.iter().enumerate() {
+            writeln!(f, "{:>3}. {}", rank + 1, candidate)?;
+        }
+        writeln!(f, "Without discarding:")?;
+        writeln!(f, "     {}", self.keep)
+    }
+}
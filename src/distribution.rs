@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fmt::{ Display, Formatter };
+
+use serde::Serialize;
+
+use crate::input::RoundInput;
+use crate::rng::GameRng;
+use crate::roundscorer::ScoreOptions;
+use crate::score_with_rng;
+
+/// Probability of a single final score
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ScoreProbability {
+    pub score: f64,
+    pub probability: f64,
+}
+
+/// Every possible final score of a round along with its exact probability
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreDistribution {
+    /// Number of random branches which were scored
+    pub branches: usize,
+    pub expected_score: f64,
+    /// Distinct final scores, lowest first
+    pub outcomes: Vec<ScoreProbability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<f64>,
+    /// Probability of beating the target, if there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pass_probability: Option<f64>,
+}
+
+/// A random roll made while scoring, and the branch taken
+struct Decision {
+    taken: usize,
+    probabilities: Vec<f64>,
+}
+
+/// Replays a fixed list of branches, then takes the first possible branch of any new roll
+struct ReplayRng {
+    forced: Vec<usize>,
+    decisions: Vec<Decision>,
+}
+
+impl ReplayRng {
+    fn decide(&mut self, probabilities: Vec<f64>) -> usize {
+        let taken = match self.forced.get(self.decisions.len()) {
+            Some(&taken) => taken,
+            None => probabilities.iter().position(|&p| p > 0.0).unwrap_or(0),
+        };
+        self.decisions.push(Decision { taken, probabilities });
+        taken
+    }
+
+    fn probability(&self) -> f64 {
+        self.decisions
+            .iter()
+            .map(|decision| decision.probabilities[decision.taken])
+            .product()
+    }
+
+    /// Branches to replay for the next unexplored path, or none once every path is done
+    fn next_path(&self) -> Option<Vec<usize>> {
+        for (index, decision) in self.decisions.iter().enumerate().rev() {
+            let next = (decision.taken + 1..decision.probabilities.len()).find(
+                |&branch| decision.probabilities[branch] > 0.0
+            );
+            if let Some(next) = next {
+                let mut path: Vec<usize> = self.decisions[..index]
+                    .iter()
+                    .map(|decision| decision.taken)
+                    .collect();
+                path.push(next);
+                return Some(path);
+            }
+        }
+        None
+    }
+}
+
+impl GameRng for ReplayRng {
+    fn roll(&mut self, sides: u32) -> u32 {
+        let sides = sides.max(1);
+        self.decide(vec![1.0 / f64::from(sides); sides as usize]) as u32
+    }
+
+    fn chance(&mut self, numerator: u32, denominator: u32) -> bool {
+        let probability = (f64::from(numerator) / f64::from(denominator.max(1))).clamp(0.0, 1.0);
+        self.decide(vec![probability, 1.0 - probability]) == 0
+    }
+
+    /// Takes the likelier result without branching, since either one ends with the same chips
+    /// and mult and would be merged
+    fn chance_unscored(&mut self, numerator: u32, denominator: u32) -> bool {
+        numerator.saturating_mul(2) > denominator
+    }
+}
+
+/// Scores every branch of the random effects in the round, merging branches which end with
+/// the same chips and mult. Rolls which only change money or cards, such as Glass cards
+/// breaking, are not branched on. Branches multiply with every roll and are only merged once
+/// scored, so this fails when there are more than `max_branches` of them.
+///
+/// Tarots and spectrals are used once with the seed of the options, so the distribution is
+/// of the scoring rolls given the cards the seed's consumables made.
+pub fn exact_distribution(
+    round: &RoundInput,
+    options: &ScoreOptions,
+    max_branches: usize
+) -> Result<ScoreDistribution, String> {
+    let options = ScoreOptions { explain: false, ..options.clone() };
+
+    // Keyed by the bits of the final chips and mult so identical states merge
+    let mut states: BTreeMap<(u64, u64), (f64, f64)> = BTreeMap::new();
+    let mut target = None;
+    let mut branches: usize = 0;
+    let mut path = Some(Vec::new());
+
+    while let Some(forced) = path {
+        branches += 1;
+        if branches > max_branches {
+            return Err(
+                format!(
+                    "Round has more than {max_branches} random branches, \
+                     raise --max-branches or use --simulate instead"
+                )
+            );
+        }
+
+        let mut rng = ReplayRng { forced, decisions: Vec::new() };
+        let report = score_with_rng(round, &options, &mut rng);
+        target = report.outcome.map(|outcome| outcome.target);
+
+        let state = states
+            .entry((report.chips.to_bits(), report.mult.to_bits()))
+            .or_insert((report.score, 0.0));
+        state.1 += rng.probability();
+
+        path = rng.next_path();
+    }
+
+    let mut scores: BTreeMap<u64, f64> = BTreeMap::new();
+    states.values().for_each(|&(score, probability)| {
+        *scores.entry(score.to_bits()).or_insert(0.0) += probability;
+    });
+
+    let mut outcomes: Vec<ScoreProbability> = scores
+        .iter()
+        .map(|(&score, &probability)| ScoreProbability { score: f64::from_bits(score), probability })
+        .collect();
+    outcomes.sort_by(|a, b| a.score.total_cmp(&b.score));
+
+    Ok(ScoreDistribution {
+        branches,
+        expected_score: outcomes
+            .iter()
+            .map(|outcome| outcome.score * outcome.probability)
+            .sum(),
+        pass_probability: target.map(|target| {
+            outcomes
+                .iter()
+                .filter(|outcome| outcome.score >= target)
+                .map(|outcome| outcome.probability)
+                .sum()
+        }),
+        target,
+        outcomes,
+    })
+}
+
+impl Display for ScoreDistribution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Branches: {}", self.branches)?;
+        writeln!(f, "Expected score: {:.2}", self.expected_score)?;
+        if let (Some(target), Some(pass_probability)) = (self.target, self.pass_probability) {
+            writeln!(f, "Beats target of {}: {:.4}%", target, pass_probability * 100.0)?;
+        }
+        for outcome in &self.outcomes {
+            writeln!(f, "  {:>12} {:>9.4}%", outcome.score, outcome.probability * 100.0)?;
+        }
+        Ok(())
+    }
+}
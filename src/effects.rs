@@ -0,0 +1,77 @@
+use std::fmt::{ Display, Formatter };
+
+use crate::cards::PlayingCard;
+use crate::consumables::Planet;
+
+/// Money, consumables and destroyed cards from playing the hand, on top of its score
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoundEffects {
+    pub money: i64,
+    pub consumables: Vec<CreatedConsumable>,
+    /// Glass cards which shattered, as they were played
+    pub destroyed_cards: Vec<PlayingCard>,
+    /// Part of `consumables` created by Purple Seals on cards discarded before the hand
+    pub discard_consumables: Vec<CreatedConsumable>,
+    /// Part of `money` paid by Gold cards held in hand at the end of the round
+    pub round_end_money: i64,
+    /// Part of `consumables` created by Blue Seals held in hand at the end of the round
+    pub round_end_consumables: Vec<CreatedConsumable>,
+}
+
+/// Consumable card created during the round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreatedConsumable {
+    Planet(Planet),
+    /// A random tarot card
+    Tarot,
+}
+
+impl RoundEffects {
+    pub fn is_empty(&self) -> bool {
+        self.money == 0 && self.consumables.is_empty() && self.destroyed_cards.is_empty()
+    }
+
+    /// Consumables created by playing the hand, without those from discards or held cards
+    pub fn hand_consumables(&self) -> Vec<CreatedConsumable> {
+        let mut consumables = self.consumables.clone();
+        self.discard_consumables
+            .iter()
+            .chain(&self.round_end_consumables)
+            .for_each(|created| {
+                if let Some(position) = consumables.iter().position(|other| other == created) {
+                    consumables.remove(position);
+                }
+            });
+        consumables
+    }
+}
+
+impl Display for CreatedConsumable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CreatedConsumable::Planet(planet) => write!(f, "{}", planet),
+            CreatedConsumable::Tarot => write!(f, "Tarot"),
+        }
+    }
+}
+
+impl Display for RoundEffects {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Earned ${}", self.money)?;
+        if !self.consumables.is_empty() {
+            let created: Vec<String> = self.consumables
+                .iter()
+                .map(|consumable| consumable.to_string())
+                .collect();
+            write!(f, ", created {}", created.join(", "))?;
+        }
+        if !self.destroyed_cards.is_empty() {
+            let destroyed: Vec<String> = self.destroyed_cards
+                .iter()
+                .map(|card| card.to_string())
+                .collect();
+            write!(f, ", destroyed {}", destroyed.join(", "))?;
+        }
+        Ok(())
+    }
+}
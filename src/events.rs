@@ -0,0 +1,139 @@
+use std::fmt::{ Display, Formatter };
+
+use ortalib::{ Chips, Edition, Mult, Rank, Suit };
+use serde::Serialize;
+
+use crate::cards::{ Enhancement, Joker, Seal };
+
+/// Rank and suit of the card an event refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardLabel {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+/// What an edition is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditionOwner {
+    Card(CardLabel),
+    Joker(Joker),
+}
+
+/// What produced a scoring event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSource {
+    /// A scored card adding its rank chips
+    Card(CardLabel),
+    /// A card held in hand
+    HeldCard(CardLabel),
+    /// A joker, along with the card it reacted to if any
+    Joker {
+        joker: Joker,
+        card: Option<CardLabel>,
+    },
+    Edition {
+        edition: Edition,
+        owner: EditionOwner,
+    },
+    Enhancement {
+        enhancement: Enhancement,
+        card: CardLabel,
+    },
+    Seal {
+        seal: Seal,
+        card: CardLabel,
+    },
+}
+
+/// How an event changed the score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    AddChips,
+    AddMult,
+    #[serde(rename = "xmult")]
+    XMult,
+    /// The card is scored again, the delta is always one
+    Retrigger,
+    /// The card is debuffed by the boss blind and scores nothing, the delta is always zero
+    Debuffed,
+}
+
+/// A single step of scoring, with the running chips and mult after it applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreEvent {
+    pub source: EventSource,
+    pub kind: EventKind,
+    pub delta: f64,
+    pub chips: Chips,
+    pub mult: Mult,
+}
+
+impl ScoreEvent {
+    pub fn new(
+        source: EventSource,
+        kind: EventKind,
+        delta: f64,
+        chips: Chips,
+        mult: Mult
+    ) -> ScoreEvent {
+        ScoreEvent { source, kind, delta, chips, mult }
+    }
+}
+
+impl EventSource {
+    /// Source for a joker acting on its own
+    pub fn joker(joker: Joker) -> EventSource {
+        EventSource::Joker { joker, card: None }
+    }
+
+    /// Source for a joker reacting to a card
+    pub fn joker_on(joker: Joker, rank: Rank, suit: Suit) -> EventSource {
+        EventSource::Joker { joker, card: Some(CardLabel { rank, suit }) }
+    }
+}
+
+impl Display for CardLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+impl Display for EditionOwner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditionOwner::Card(card) => write!(f, "{}", card),
+            EditionOwner::Joker(joker) => write!(f, "{}", joker),
+        }
+    }
+}
+
+impl Display for EventSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventSource::Card(card) | EventSource::HeldCard(card) => write!(f, "{}", card),
+            EventSource::Joker { joker, card: Some(card) } => write!(f, "{} {}", joker, card),
+            EventSource::Joker { joker, card: None } => write!(f, "{}", joker),
+            EventSource::Edition { edition, owner } => write!(f, "{} {}", owner, edition),
+            EventSource::Enhancement { enhancement, card } => write!(f, "{} {}", card, enhancement),
+            EventSource::Seal { seal, card } => write!(f, "{} {}", card, seal),
+        }
+    }
+}
+
+impl Display for ScoreEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            EventKind::AddChips => write!(f, "{} +{} Chips", self.source, self.delta)?,
+            EventKind::AddMult => write!(f, "{} +{} Mult", self.source, self.delta)?,
+            EventKind::XMult => write!(f, "{} x{} Mult", self.source, self.delta)?,
+            EventKind::Retrigger => {
+                return write!(f, "{} Retrigger", self.source);
+            }
+            EventKind::Debuffed => {
+                return write!(f, "{} debuffed", self.source);
+            }
+        }
+        write!(f, " ( {:?} x {:?} )", self.chips, self.mult)
+    }
+}
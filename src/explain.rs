@@ -0,0 +1,236 @@
+use ortalib::{ Chips, Mult };
+use serde::Serialize;
+
+use crate::events::{ EditionOwner, EventKind, EventSource, ScoreEvent };
+use crate::input::RoundInput;
+use crate::roundscorer::ScoreReport;
+use crate::target::TargetOutcome;
+
+/// Version of the explain document layout. Bumped whenever a field is
+/// renamed, removed or changes meaning, new optional fields do not bump it.
+///
+/// Version 2 adds the `debuffed` step kind and `seal` step sources, which readers of
+/// version 1 would not expect, along with the hand level, consumables, boss blind, card
+/// changes, violation, target and effects. Its `destroyed_cards` are whole cards with their
+/// enhancement, edition and seal.
+pub const EXPLAIN_SCHEMA_VERSION: u32 = 2;
+
+/// Machine readable explanation of how a round was scored
+#[derive(Debug, Clone, Serialize)]
+pub struct ExplainDocument {
+    pub schema_version: u32,
+    pub input: InputSummary,
+    pub hand: HandSummary,
+    pub steps: Vec<ExplainStep>,
+    pub result: ScoreSummary,
+}
+
+/// Cards and jokers the round was scored with
+#[derive(Debug, Clone, Serialize)]
+pub struct InputSummary {
+    pub cards_played: Vec<String>,
+    pub cards_held_in_hand: Vec<String>,
+    pub jokers: Vec<String>,
+    pub consumables: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boss_blind: Option<String>,
+    /// Cards and jokers changed by tarots and spectrals, the hand was scored with the cards
+    /// after the change
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub card_changes: Vec<CardChangeSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CardChangeSummary {
+    pub consumable: String,
+    /// Missing when the card was created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Missing when the card was destroyed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// Detected poker hand and the chips and mult it starts scoring from
+#[derive(Debug, Clone, Serialize)]
+pub struct HandSummary {
+    pub name: String,
+    pub level: u32,
+    pub chips: Chips,
+    pub mult: Mult,
+    pub scoring_cards: Vec<String>,
+}
+
+/// A single scoring step, in the order it was applied
+#[derive(Debug, Clone, Serialize)]
+pub struct ExplainStep {
+    pub source: StepSource,
+    pub kind: EventKind,
+    pub delta: f64,
+    pub chips: Chips,
+    pub mult: Mult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StepSource {
+    Card {
+        card: String,
+    },
+    HeldCard {
+        card: String,
+    },
+    Joker {
+        joker: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        card: Option<String>,
+    },
+    Edition {
+        edition: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        card: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        joker: Option<String>,
+    },
+    Enhancement {
+        enhancement: String,
+        card: String,
+    },
+    Seal {
+        seal: String,
+        card: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreSummary {
+    pub chips: Chips,
+    pub mult: Mult,
+    pub score: f64,
+    /// Why the boss blind did not allow the hand
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<TargetOutcome>,
+    pub effects: EffectsSummary,
+}
+
+/// Money and consumables earned on top of the score
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectsSummary {
+    pub money: i64,
+    pub consumables: Vec<String>,
+    pub destroyed_cards: Vec<String>,
+}
+
+impl ExplainDocument {
+    pub fn new(round: &RoundInput, report: &ScoreReport) -> ExplainDocument {
+        ExplainDocument {
+            schema_version: EXPLAIN_SCHEMA_VERSION,
+            input: InputSummary {
+                cards_played: round.cards_played
+                    .iter()
+                    .map(|card| card.to_string())
+                    .collect(),
+                cards_held_in_hand: round.cards_held_in_hand
+                    .iter()
+                    .map(|card| card.to_string())
+                    .collect(),
+                jokers: round.jokers
+                    .iter()
+                    .map(|joker| joker.to_string())
+                    .collect(),
+                consumables: round.consumables
+                    .iter()
+                    .map(|consumable| consumable.to_string())
+                    .collect(),
+                boss_blind: round.boss_blind.map(|blind| blind.to_string()),
+                card_changes: report.card_changes
+                    .iter()
+                    .map(|change| CardChangeSummary {
+                        consumable: change.by.to_string(),
+                        before: change.before.map(|card| card.to_string()),
+                        after: change.after.map(|card| card.to_string()),
+                    })
+                    .collect(),
+            },
+            hand: HandSummary {
+                name: report.hand_name(),
+                level: report.hand_level,
+                chips: report.base_chips,
+                mult: report.base_mult,
+                scoring_cards: report.scored_cards
+                    .iter()
+                    .map(|card| card.label().to_string())
+                    .collect(),
+            },
+            steps: report.events.iter().map(ExplainStep::from).collect(),
+            result: ScoreSummary {
+                chips: report.chips,
+                mult: report.mult,
+                score: report.score,
+                violation: report.violation.map(|violation| violation.to_string()),
+                target: report.outcome,
+                effects: EffectsSummary {
+                    money: report.effects.money,
+                    consumables: report.effects.consumables
+                        .iter()
+                        .map(|consumable| consumable.to_string())
+                        .collect(),
+                    destroyed_cards: report.effects.destroyed_cards
+                        .iter()
+                        .map(|card| card.to_string())
+                        .collect(),
+                },
+            },
+        }
+    }
+}
+
+impl From<&ScoreEvent> for ExplainStep {
+    fn from(event: &ScoreEvent) -> ExplainStep {
+        ExplainStep {
+            source: StepSource::from(&event.source),
+            kind: event.kind,
+            delta: event.delta,
+            chips: event.chips,
+            mult: event.mult,
+        }
+    }
+}
+
+impl From<&EventSource> for StepSource {
+    fn from(source: &EventSource) -> StepSource {
+        match source {
+            EventSource::Card(card) => StepSource::Card { card: card.to_string() },
+            EventSource::HeldCard(card) => StepSource::HeldCard { card: card.to_string() },
+            EventSource::Joker { joker, card } =>
+                StepSource::Joker {
+                    joker: joker.to_string(),
+                    card: card.map(|card| card.to_string()),
+                },
+            EventSource::Edition { edition, owner: EditionOwner::Card(card) } =>
+                StepSource::Edition {
+                    edition: edition.to_string(),
+                    card: Some(card.to_string()),
+                    joker: None,
+                },
+            EventSource::Edition { edition, owner: EditionOwner::Joker(joker) } =>
+                StepSource::Edition {
+                    edition: edition.to_string(),
+                    card: None,
+                    joker: Some(joker.to_string()),
+                },
+            EventSource::Enhancement { enhancement, card } =>
+                StepSource::Enhancement {
+                    enhancement: enhancement.to_string(),
+                    card: card.to_string(),
+                },
+            EventSource::Seal { seal, card } =>
+                StepSource::Seal {
+                    seal: seal.to_string(),
+                    card: card.to_string(),
+                },
+        }
+    }
+}
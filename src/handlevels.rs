@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use ortalib::{ Chips, Mult, PokerHand };
+use serde::Deserialize;
+
+use crate::utils::parse_poker_hand;
+
+/// Level of each poker hand, hands which are not listed are level 1
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "HashMap<String, u32>")]
+pub struct HandLevels {
+    levels: HashMap<PokerHand, u32>,
+}
+
+impl HandLevels {
+    pub fn level(&self, hand: PokerHand) -> u32 {
+        *self.levels.get(&hand).unwrap_or(&1)
+    }
+
+    pub fn set_level(&mut self, hand: PokerHand, level: u32) {
+        self.levels.insert(hand, level.max(1));
+    }
+
+    /// Levels up a hand, as a planet card would
+    pub fn level_up(&mut self, hand: PokerHand) {
+        self.set_level(hand, self.level(hand) + 1);
+    }
+
+    /// Returns the base chips and mult of a hand at its current level
+    pub fn hand_value(&self, hand: PokerHand) -> (Chips, Mult) {
+        hand_value_at(hand, self.level(hand))
+    }
+}
+
+impl TryFrom<HashMap<String, u32>> for HandLevels {
+    type Error = String;
+
+    fn try_from(value: HashMap<String, u32>) -> Result<Self, Self::Error> {
+        let mut levels = HashMap::new();
+        for (name, level) in value {
+            if level == 0 {
+                return Err(format!("Hand level for `{name}` must be at least 1"));
+            }
+            levels.insert(parse_poker_hand(&name)?, level);
+        }
+        Ok(HandLevels { levels })
+    }
+}
+
+/// Chips and mult gained by a poker hand for each level above 1
+pub fn level_increment(hand: PokerHand) -> (Chips, Mult) {
+    use PokerHand::*;
+
+    #[rustfmt::skip]
+    let (chips, mult) = match hand {
+        HighCard =>      (10, 1),
+        Pair =>          (15, 1),
+        TwoPair =>       (20, 1),
+        ThreeOfAKind =>  (20, 2),
+        Straight =>      (30, 3),
+        Flush =>         (15, 2),
+        FullHouse =>     (25, 2),
+        FourOfAKind =>   (30, 3),
+        StraightFlush => (40, 4),
+        FiveOfAKind =>   (35, 3),
+        FlushHouse =>    (40, 4),
+        FlushFive =>     (50, 3),
+    };
+
+    (chips.into(), mult.into())
+}
+
+/// Returns the base chips and mult of a poker hand at the given level
+pub fn hand_value_at(hand: PokerHand, level: u32) -> (Chips, Mult) {
+    let (base_chips, base_mult) = hand.hand_value();
+    let (chips_per_level, mult_per_level) = level_increment(hand);
+    let extra_levels = f64::from(level.max(1) - 1);
+
+    (base_chips + chips_per_level * extra_levels, base_mult + mult_per_level * extra_levels)
+}
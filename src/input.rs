@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use ortalib::{ PokerHand, Round };
+use serde::Deserialize;
+
+use crate::blinds::BossBlind;
+use crate::cards::{ JokerCard, PlayingCard };
+use crate::consumables::Consumable;
+use crate::handlevels::HandLevels;
+use crate::jokerstates::JokerStates;
+use crate::run::RunConfig;
+use crate::target::{ BlindType, Stake, blind_target };
+use crate::utils::{ deserialize_poker_hands, parse_poker_hand };
+
+/// A round as read from the input file. Extends `ortalib::Round` with the
+/// optional sections the scorer understands.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoundInput {
+    pub cards_played: Vec<PlayingCard>,
+
+    #[serde(default)]
+    pub cards_held_in_hand: Vec<PlayingCard>,
+
+    #[serde(default)]
+    pub jokers: Vec<JokerCard>,
+
+    /// Level of each poker hand, keyed by hand name such as `Flush House`
+    #[serde(default)]
+    pub hand_levels: HandLevels,
+
+    /// Consumables used before the hand is scored, in order
+    #[serde(default)]
+    pub consumables: Vec<Consumable>,
+
+    /// Boss blind the hand is played against
+    #[serde(default)]
+    pub boss_blind: Option<BossBlind>,
+
+    /// Cards played earlier this ante, debuffed by The Pillar
+    #[serde(default)]
+    pub cards_previously_played: Vec<PlayingCard>,
+
+    /// Cards discarded this round, Purple Seals among them create tarot cards
+    #[serde(default)]
+    pub cards_discarded: Vec<PlayingCard>,
+
+    /// Index of the joker disabled by Crimson Heart, defaults to the first joker
+    #[serde(default)]
+    pub disabled_joker: Option<usize>,
+
+    /// What already happened earlier in the round
+    #[serde(default)]
+    pub history: RoundHistory,
+
+    /// What was played earlier in the run, the hands played this round are in `history`
+    #[serde(default)]
+    pub run_history: RunHistory,
+
+    /// Score needed to beat the blind, takes priority over `ante`
+    #[serde(default)]
+    pub target: Option<f64>,
+
+    /// Ante the blind is in, used with `stake` and `blind` to find the target
+    #[serde(default)]
+    pub ante: Option<u32>,
+
+    #[serde(default)]
+    pub stake: Option<Stake>,
+
+    /// Defaults to the boss blind when `boss_blind` is set, otherwise the small blind
+    #[serde(default)]
+    pub blind: Option<BlindType>,
+
+    /// Every card in the deck, defaults to the standard 52 cards
+    #[serde(default)]
+    pub full_deck: Option<Vec<PlayingCard>>,
+
+    /// Cards still in the deck, worked out from `full_deck` and the cards seen when empty
+    #[serde(default)]
+    pub deck: Vec<PlayingCard>,
+
+    #[serde(default)]
+    pub hands_remaining: Option<u32>,
+
+    #[serde(default)]
+    pub discards_remaining: Option<u32>,
+
+    /// Resources of the round when the hand is played, see `RoundInput::context`
+    #[serde(default)]
+    pub context: Option<RoundContextInput>,
+
+    /// What scaling jokers such as Green Joker built up in earlier hands
+    #[serde(default)]
+    pub joker_states: JokerStates,
+}
+
+impl RoundInput {
+    /// Round with only the given cards and jokers, every optional section left out
+    pub fn new(
+        cards_played: Vec<PlayingCard>,
+        cards_held_in_hand: Vec<PlayingCard>,
+        jokers: Vec<JokerCard>
+    ) -> RoundInput {
+        RoundInput {
+            cards_played,
+            cards_held_in_hand,
+            jokers,
+            hand_levels: HandLevels::default(),
+            consumables: Vec::new(),
+            boss_blind: None,
+            cards_previously_played: Vec::new(),
+            cards_discarded: Vec::new(),
+            disabled_joker: None,
+            history: RoundHistory::default(),
+            run_history: RunHistory::default(),
+            target: None,
+            ante: None,
+            stake: None,
+            blind: None,
+            full_deck: None,
+            deck: Vec::new(),
+            hands_remaining: None,
+            discards_remaining: None,
+            context: None,
+            joker_states: JokerStates::default(),
+        }
+    }
+
+    /// Score needed to beat the blind, if the round has a target or an ante
+    pub fn blind_target(&self) -> Option<f64> {
+        if self.target.is_some() {
+            return self.target;
+        }
+
+        let context = self.context.unwrap_or_default();
+        let blind = self.blind.or(context.blind).unwrap_or(match self.boss_blind {
+            Some(_) => BlindType::Boss,
+            None => BlindType::Small,
+        });
+        self.ante
+            .or(context.ante)
+            .map(|ante| blind_target(ante, self.stake.unwrap_or_default(), blind))
+    }
+
+    /// Resources of the round, taken from the `context` block, then the matching top level
+    /// fields, then the start of a blind in a default run
+    pub fn context(&self) -> RoundContext {
+        let input = self.context.unwrap_or_default();
+        let fresh = RunConfig::default();
+        RoundContext {
+            hands_left: input.hands_left.or(self.hands_remaining).unwrap_or(fresh.hands),
+            discards_left: input.discards_left
+                .or(self.discards_remaining)
+                .unwrap_or(fresh.discards),
+            hand_size: input.hand_size.unwrap_or(fresh.hand_size),
+            money: input.money.unwrap_or(fresh.money),
+            ante: input.ante.or(self.ante).unwrap_or(1),
+            blind: input.blind.or(self.blind).unwrap_or(match self.boss_blind {
+                Some(_) => BlindType::Boss,
+                None => BlindType::Small,
+            }),
+        }
+    }
+}
+
+/// Round resources as written in the input, such as
+///
+/// ```yaml
+/// context:
+///   hands_left: 1
+///   discards_left: 0
+///   money: 12
+///   blind: Big
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoundContextInput {
+    /// Hands left to play, counting the hand being scored
+    pub hands_left: Option<u32>,
+    pub discards_left: Option<u32>,
+    pub hand_size: Option<usize>,
+    pub money: Option<i64>,
+    pub ante: Option<u32>,
+    pub blind: Option<BlindType>,
+}
+
+/// Resources of the round when the hand is played, for jokers such as Banner and Acrobat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundContext {
+    /// Hands left to play, counting the hand being scored
+    pub hands_left: u32,
+    pub discards_left: u32,
+    pub hand_size: usize,
+    pub money: i64,
+    pub ante: u32,
+    pub blind: BlindType,
+}
+
+impl RoundContext {
+    /// Whether the hand being scored is the last one of the round
+    pub fn is_final_hand(&self) -> bool {
+        self.hands_left <= 1
+    }
+}
+
+/// Earlier events in the round, used by blinds which depend on them
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RoundHistory {
+    /// Poker hands already played this round, in order, for The Eye and The Mouth
+    #[serde(default, deserialize_with = "deserialize_poker_hands")]
+    pub hands_played_this_round: Vec<PokerHand>,
+
+    /// Whether a joker was sold this round, which lifts Verdant Leaf
+    #[serde(default)]
+    pub joker_sold: bool,
+}
+
+/// Earlier events in the run, used by jokers such as Supernova and Obelisk
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RunHistoryInput")]
+pub struct RunHistory {
+    play_counts: HashMap<PokerHand, u32>,
+}
+
+/// Run history as written in the input, such as `play_counts: { Pair: 3, Flush: 1 }`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunHistoryInput {
+    #[serde(default)]
+    play_counts: HashMap<String, u32>,
+}
+
+impl RunHistory {
+    /// Times the poker hand was played earlier in the run, counting this round
+    pub fn play_count(&self, hand: PokerHand) -> u32 {
+        self.play_counts.get(&hand).copied().unwrap_or(0)
+    }
+
+    /// Number of hands played earlier in the run
+    pub fn hands_played(&self) -> u32 {
+        self.play_counts.values().sum()
+    }
+
+    pub fn record(&mut self, hand: PokerHand) {
+        *self.play_counts.entry(hand).or_insert(0) += 1;
+    }
+
+    /// Takes back a hand recorded with `record`
+    /// Whether this poker hand has been played more often than any other, so a tie for most
+    /// played is not enough
+    pub fn is_most_played(&self, hand: PokerHand) -> bool {
+        let count = self.play_count(hand);
+        count > 0 &&
+            self.play_counts
+                .iter()
+                .all(|(&other, &other_count)| other == hand || other_count < count)
+    }
+}
+
+impl TryFrom<RunHistoryInput> for RunHistory {
+    type Error = String;
+
+    fn try_from(input: RunHistoryInput) -> Result<Self, Self::Error> {
+        let mut play_counts = HashMap::new();
+        for (name, count) in input.play_counts {
+            play_counts.insert(parse_poker_hand(&name)?, count);
+        }
+        Ok(RunHistory { play_counts })
+    }
+}
+
+impl From<&Round> for RoundInput {
+    fn from(round: &Round) -> Self {
+        RoundInput::new(
+            round.cards_played.iter().map(PlayingCard::from).collect(),
+            round.cards_held_in_hand.iter().map(PlayingCard::from).collect(),
+            round.jokers.iter().map(JokerCard::from).collect()
+        )
+    }
+}
@@ -1,23 +1,25 @@
 use ortalib::{
     Chips,
     Edition,
-    Enhancement,
-    Joker,
-    JokerCard,
     Mult,
     PokerHand,
     Rank,
     Suit,
     SuitColor,
 };
+use crate::cards::{ Enhancement, Joker, JokerCard, PlayingCard };
 use crate::cardscorer::{ HandCard, ScoringData, ScoringPlayedCard };
+use crate::handlevels::HandLevels;
+use crate::jokerstates::{ JokerState, JokerStates };
 use crate::modifiers::{ get_foil_holo, get_polychrome };
+use crate::events::{ EditionOwner, EventKind, EventSource, ScoreEvent };
 use std::collections::HashMap;
+use std::sync::{ Arc, LazyLock, RwLock };
 
+/// Scoring stages a joker card takes part in, each looks up the joker's `JokerEffect`
 pub trait JokerCardCalculator {
     fn calculate_independent_cards(
         &self,
-        explain: &bool,
         curr_chips: &Chips,
         curr_mult: &Mult,
         data: &ScoringData
@@ -25,644 +27,1215 @@ pub trait JokerCardCalculator {
 
     fn calculate_on_played_cards(
         &self,
-        explain: &bool,
         current_chips: &Chips,
         current_mult: &Mult,
         played_card: &ScoringPlayedCard,
-        scored_card_index: usize,
         data: &ScoringData
     ) -> (Chips, Mult);
 
     fn calculate_on_held_cards(
         &self,
-        explain: &bool,
         current_chips: &Chips,
         current_mult: &Mult,
         held_card: &HandCard,
         data: &ScoringData
     ) -> (Chips, Mult);
-    fn set_splash(&self, data: &mut ScoringData);
-    fn set_pareidolia(&self, data: &mut ScoringData);
+
+    fn setup(&self, data: &mut ScoringData);
+
+    fn retrigger_count(&self, card: RetriggeredCard, data: &ScoringData) -> u32;
+}
+
+/// Behaviour of a single joker. Every hook does nothing unless the joker overrides it.
+pub trait JokerEffect: Send + Sync {
+    /// Changes how the played hand is evaluated
+    fn modify_evaluation(&self, _rules: &mut EvaluationRules) {}
+
+    /// Runs once the poker hand is found, before its chips and mult are looked up. Custom
+    /// hands have no level, so it only runs for poker hands.
+    fn modify_hand_levels(&self, _hand: PokerHand, _levels: &mut HandLevels, _data: &ScoringData) {}
+
+    /// Runs once the poker hand is found, before any card scores
+    fn setup(&self, _data: &mut ScoringData) {}
+
+    /// State of a scaling joker before it has built anything up
+    fn initial_state(&self) -> JokerState {
+        JokerState::default()
+    }
+
+    /// Builds up the joker's state from the played hand, before any card scores
+    fn before_scoring(&self, _states: &mut JokerStates, _data: &ScoringData) {}
+
+    /// Builds up the joker's state each time a played card scores, so once for every retrigger.
+    /// Stone cards never reach it.
+    fn on_scored_card_state(
+        &self,
+        _states: &mut JokerStates,
+        _card: &ScoringPlayedCard,
+        _data: &ScoringData
+    ) {}
+
+    /// Changes the joker's state once every card has scored
+    fn after_scoring(&self, _states: &mut JokerStates, _data: &ScoringData) {}
+
+    /// Changes the joker's state when cards are discarded between hands
+    fn on_discard(&self, _states: &mut JokerStates, _cards: &[PlayingCard]) {}
+
+    /// Runs each time a played card scores, stone cards never reach jokers
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        _card: &ScoringPlayedCard,
+        _data: &ScoringData
+    ) -> (Chips, Mult) {
+        (*current_chips, *current_mult)
+    }
+
+    /// Runs each time a card held in hand scores, stone cards never reach jokers
+    fn on_held_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        _card: &HandCard,
+        _data: &ScoringData
+    ) -> (Chips, Mult) {
+        (*current_chips, *current_mult)
+    }
+
+    /// Number of extra times the card is scored
+    fn retrigger_count(&self, _card: RetriggeredCard, _data: &ScoringData) -> u32 {
+        0
+    }
+
+    /// Runs once after every card has scored, between the joker's editions
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        _data: &ScoringData
+    ) -> (Chips, Mult) {
+        (*current_chips, *current_mult)
+    }
+}
+
+/// Rules for evaluating the played hand which jokers can change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvaluationRules {
+    /// Flushes and straights need only 4 cards
+    pub four_fingers: bool,
+    /// Straights can skip a single rank
+    pub shortcut: bool,
+    /// Hearts and Diamonds count as the same suit, as do Spades and Clubs
+    pub smeared: bool,
+    /// Every card with a rank is a face card
+    pub all_faces: bool,
+    /// Every played card scores, not just the cards making up the poker hand
+    pub every_card_scores: bool,
+}
+
+/// Card which a joker may retrigger
+#[derive(Debug, Clone, Copy)]
+pub enum RetriggeredCard<'a> {
+    Scored(&'a ScoringPlayedCard),
+    Held(&'a HandCard),
+}
+
+/// Effects of every joker, keyed by joker
+#[derive(Clone, Default)]
+pub struct JokerRegistry {
+    effects: HashMap<Joker, Arc<dyn JokerEffect>>,
 }
 
+/// Registry used while scoring, starts with the built-in jokers
+static REGISTRY: LazyLock<RwLock<JokerRegistry>> = LazyLock::new(|| {
+    RwLock::new(JokerRegistry::builtin())
+});
+
 impl JokerCardCalculator for JokerCard {
-    fn set_splash(&self, data: &mut ScoringData) {
-        match self.joker {
-            Joker::Splash => {
-                let new_scored_cards: Vec<ScoringPlayedCard> = data.played_cards
-                    .iter()
-                    .enumerate()
-                    .map(|(index, card)| ScoringPlayedCard {
-                        rank: card.rank,
-                        suit: card.suit,
-                        enhancement: card.enhancement,
-                        edition: card.edition,
-                        scored_card_index: index,
-                        is_face: card.rank.is_face(),
-                    })
-                    .collect();
-
-                data.scored_cards = new_scored_cards;
-            }
-            _ => {}
+    fn setup(&self, data: &mut ScoringData) {
+        if let Some(effect) = data.joker_effect(self.joker) {
+            effect.setup(data);
         }
     }
 
-    fn set_pareidolia(&self, data: &mut ScoringData) {
-        match self.joker {
-            Joker::Pareidolia => {
-                data.scored_cards.iter_mut().for_each(|card| {
-                    card.is_face = true;
-                });
-            }
-            _ => {}
-        }
+    fn retrigger_count(&self, card: RetriggeredCard, data: &ScoringData) -> u32 {
+        data.joker_effect(self.joker).map_or(0, |effect| effect.retrigger_count(card, data))
     }
 
     fn calculate_independent_cards(
         &self,
-        explain: &bool,
         current_chips: &Chips,
         current_mult: &Mult,
         data: &ScoringData
     ) -> (Chips, Mult) {
-        let mut curr_chips: Chips = *current_chips;
-        let mut curr_mult: Mult = *current_mult;
-
-        let pokerhands = &data.pokerhands;
-        let cards_in_hand = &data.hand_cards;
-        let scored_cards = &data.scored_cards;
-        let joker_cards = &data.joker_cards;
-
-        let card_title = format!("{:?}", self.joker);
         let (edition_chips, edition_mult) = get_foil_holo(
             self.edition,
-            &card_title,
-            explain,
-            &curr_chips,
-            &curr_mult
+            EditionOwner::Joker(self.joker),
+            current_chips,
+            current_mult,
+            data
         );
 
-        let mut is_smeared: bool = false;
-
-        data.joker_cards.iter().for_each(|joker_card| {
-            match joker_card.joker {
-                Joker::SmearedJoker => {
-                    is_smeared = true;
-                }
-                _ => {}
-            }
-        });
-
-        curr_chips = edition_chips;
-        curr_mult = edition_mult;
+        let (curr_chips, curr_mult) = match data.joker_effect(self.joker) {
+            Some(effect) => effect.independent(&edition_chips, &edition_mult, data),
+            None => (edition_chips, edition_mult),
+        };
 
-        match self.joker {
-            Joker::Joker => {
-                curr_mult += 4.0;
-                if *explain {
-                    println!("Joker +4 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                }
-            }
-            Joker::JollyJoker => {
-                if pokerhands.contains(&PokerHand::Pair) {
-                    curr_mult += 8.0;
-                    if *explain {
-                        println!("Jolly Joker +8 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::ZanyJoker => {
-                if pokerhands.contains(&PokerHand::ThreeOfAKind) {
-                    curr_mult += 12.0;
-                    if *explain {
-                        println!("Zany Joker +12 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::MadJoker => {
-                if pokerhands.contains(&PokerHand::TwoPair) {
-                    curr_mult += 10.0;
-                    if *explain {
-                        println!("Mad Joker +10 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::CrazyJoker => {
-                if pokerhands.contains(&PokerHand::Straight) {
-                    curr_mult += 12.0;
-                    if *explain {
-                        println!("Crazy Joker +12 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::DrollJoker => {
-                if pokerhands.contains(&PokerHand::Flush) {
-                    curr_mult += 10.0;
-                    if *explain {
-                        println!("Droll Joker +10 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::SlyJoker => {
-                if pokerhands.contains(&PokerHand::Pair) {
-                    curr_chips += 50.0;
-                    if *explain {
-                        println!("Sly Joker +50 Chips ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::WilyJoker => {
-                if pokerhands.contains(&PokerHand::ThreeOfAKind) {
-                    curr_chips += 100.0;
-                    if *explain {
-                        println!("Wily Joker +100 Chips ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::CleverJoker => {
-                if pokerhands.contains(&PokerHand::TwoPair) {
-                    curr_chips += 80.0;
-                    if *explain {
-                        println!("Clever Joker +80 Chips ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::DeviousJoker => {
-                if pokerhands.contains(&PokerHand::Straight) {
-                    curr_chips += 100.0;
-                    if *explain {
-                        println!("Devious Joker +100 Chips ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::CraftyJoker => {
-                if pokerhands.contains(&PokerHand::Flush) {
-                    curr_chips += 80.0;
-                    if *explain {
-                        println!("Crafty Joker +80 Chips ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::AbstractJoker => {
-                curr_mult += 3.0 * (joker_cards.len() as f64);
-                if *explain {
-                    println!("Abstract Joker +3 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                }
-            }
-            Joker::Blackboard => {
-                let contains_red_suit = cards_in_hand
-                    .iter()
-                    .any(|card| {
-                        (card.suit == Suit::Diamonds || card.suit == Suit::Hearts) &&
-                            card.enhancement != Some(Enhancement::Wild)
-                    });
-
-                if !contains_red_suit {
-                    curr_mult *= 3.0;
-                    if *explain {
-                        println!("Blackboard Joker x3 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                }
-            }
-            Joker::FlowerPot => {
-                let mut num_wild: u8 = 0;
-                let mut suit_count: HashMap<Suit, u8> = HashMap::new();
-                let mut colour_count: HashMap<SuitColor, u8> = HashMap::new();
-                scored_cards.iter().for_each(|card| {
-                    if let Some(enhancement) = card.enhancement {
-                        if enhancement == Enhancement::Wild {
-                            num_wild += 1;
-                            *suit_count.entry(Suit::Clubs).or_insert(0) += 1;
-                            *suit_count.entry(Suit::Diamonds).or_insert(0) += 1;
-                            *suit_count.entry(Suit::Spades).or_insert(0) += 1;
-                            *suit_count.entry(Suit::Hearts).or_insert(0) += 1;
-                            *colour_count.entry(SuitColor::Black).or_insert(0) += 1;
-                            *colour_count.entry(SuitColor::Red).or_insert(0) += 1;
-                        } else {
-                            *suit_count.entry(card.suit).or_insert(0) += 1;
-                            *colour_count.entry(card.suit.color()).or_insert(0) += 1;
-                        }
-                    } else {
-                        *suit_count.entry(card.suit).or_insert(0) += 1;
-                        *colour_count.entry(card.suit.color()).or_insert(0) += 1;
-                    }
-                });
-                if is_smeared && num_wild == 0 {
-                    // Check for atleast 2 Reds and 2 Blacks
-                    if
-                        colour_count
-                            .iter()
-                            .filter(|&(_, &count)| count >= 2)
-                            .count() == 2
-                    {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    }
-                } else if is_smeared && num_wild != 0 {
-                    if num_wild >= 4 {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    } else if
-                        num_wild == 3 &&
-                        colour_count
-                            .iter()
-                            .filter(|&(_, &value)| value > 4)
-                            .count() >= 1
-                    {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    } else if
-                        num_wild == 2 &&
-                        (colour_count
-                            .iter()
-                            .filter(|&(_, &value)| value >= 3)
-                            .count() == 2 ||
-                            colour_count
-                                .iter()
-                                .filter(|&(_, &value)| value >= 4)
-                                .count() >= 1)
-                    {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    } else if
-                        num_wild == 1 &&
-                        colour_count
-                            .iter()
-                            .filter(|&(_, &value)| value >= 3)
-                            .count() >= 1 &&
-                        colour_count
-                            .iter()
-                            .filter(|&(_, &value)| value >= 2)
-                            .count() == 2
-                    {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    }
-                } else if suit_count.len() == 4 && num_wild == 0 {
-                    curr_mult *= 3.0;
-                    if *explain {
-                        println!("Flower Pot Joker x3 Mult ( {:?} x {:?} )", curr_chips, curr_mult);
-                    }
-                } else if num_wild != 0 {
-                    if num_wild >= 4 {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    } else if
-                        num_wild == 3 &&
-                        suit_count
-                            .iter()
-                            .filter(|&(_, &value)| value > 3)
-                            .count() >= 1
-                    {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    } else if
-                        num_wild == 2 &&
-                        suit_count
-                            .iter()
-                            .filter(|&(_, &value)| value > 2)
-                            .count() >= 2
-                    {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    } else if
-                        num_wild == 1 &&
-                        suit_count
-                            .iter()
-                            .filter(|&(_, &value)| value > 1)
-                            .count() >= 3
-                    {
-                        curr_mult *= 3.0;
-                        if *explain {
-                            println!(
-                                "Flower Pot Joker x3 Mult ( {:?} x {:?} )",
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    }
-                }
-            }
-            Joker::Blueprint => {
-                if pokerhands.contains(&PokerHand::Flush) {
-                    curr_chips += 80.0;
-                    if *explain {
-                        println!(
-                            "Blueprint Joker +80 Chips ( {:?} x {:?} )",
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            _ => {}
-        }
         let (final_chips, final_mult) = match self.edition {
             Some(Edition::Polychrome) =>
-                get_polychrome(&card_title, explain, &curr_chips, &curr_mult),
+                get_polychrome(EditionOwner::Joker(self.joker), &curr_chips, &curr_mult, data),
             _ => (curr_chips, curr_mult),
         };
 
-        return (final_chips, final_mult);
+        (final_chips, final_mult)
     }
 
     fn calculate_on_played_cards(
         &self,
-        explain: &bool,
         current_chips: &Chips,
         current_mult: &Mult,
         played_card: &ScoringPlayedCard,
-        scored_card_index: usize,
         data: &ScoringData
     ) -> (Chips, Mult) {
-        let mut curr_chips: Chips = *current_chips;
-        let mut curr_mult: Mult = *current_mult;
-
-        let scored_cards = &data.scored_cards;
-        let mut is_smeared: bool = false;
-        data.joker_cards.iter().for_each(|joker_card| {
-            match joker_card.joker {
-                Joker::SmearedJoker => {
-                    is_smeared = true;
-                }
-                _ => {}
-            }
-        });
-
-        match self.joker {
-            Joker::GreedyJoker => {
-                if
-                    played_card.suit == Suit::Diamonds ||
-                    played_card.enhancement == Some(Enhancement::Wild) ||
-                    (is_smeared && played_card.suit == Suit::Hearts)
-                {
-                    curr_mult += 3.0;
-                    if *explain {
-                        println!(
-                            "Greedy Joker {:?}{:?} +3 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::LustyJoker => {
-                if
-                    played_card.suit == Suit::Hearts ||
-                    played_card.enhancement == Some(Enhancement::Wild) ||
-                    (is_smeared && played_card.suit == Suit::Diamonds)
-                {
-                    curr_mult += 3.0;
-                    if *explain {
-                        println!(
-                            "Lusty Joker {:?}{:?} +3 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::WrathfulJoker => {
-                if
-                    played_card.suit == Suit::Spades ||
-                    played_card.enhancement == Some(Enhancement::Wild) ||
-                    (is_smeared && played_card.suit == Suit::Clubs)
-                {
-                    curr_mult += 3.0;
-                    if *explain {
-                        println!(
-                            "Wrathful Joker {:?}{:?} +3 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::GluttonousJoker => {
-                if
-                    played_card.suit == Suit::Clubs ||
-                    played_card.enhancement == Some(Enhancement::Wild) ||
-                    (is_smeared && played_card.suit == Suit::Spades)
-                {
-                    curr_mult += 3.0;
-                    if *explain {
-                        println!(
-                            "Gluttonous Joker {:?}{:?} +3 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::Fibonacci => {
-                if
-                    played_card.rank == Rank::Ace ||
-                    played_card.rank == Rank::Two ||
-                    played_card.rank == Rank::Three ||
-                    played_card.rank == Rank::Five ||
-                    played_card.rank == Rank::Eight
-                {
-                    curr_mult += 8.0;
-                    if *explain {
-                        println!(
-                            "Fibonacci Joker {:?}{:?} +8 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::ScaryFace => {
-                if played_card.is_face {
-                    curr_chips += 30.0;
-                    if *explain {
-                        println!(
-                            "Scary Joker {:?}{:?} +30 Chips ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::EvenSteven => {
-                if !played_card.rank.is_face() && played_card.rank.rank_value() % 2.0 == 0.0 {
-                    curr_mult += 4.0;
-                    if *explain {
-                        println!(
-                            "Even Steven Joker {:?}{:?} +4 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::OddTodd => {
-                if !played_card.rank.is_face() && played_card.rank.rank_value() % 2.0 == 1.0 {
-                    curr_chips += 31.0;
-                    if *explain {
-                        println!(
-                            "Odd Todd Joker {:?}{:?} +31 Chips ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::Photograph => {
-                let first_face_card_index = scored_cards.iter().position(|card| card.is_face);
-                if *explain {
-                    println!("{:?} {:?}", first_face_card_index, scored_card_index);
-                }
-                if first_face_card_index == Some(scored_card_index) {
-                    curr_mult *= 2.0;
-                    if *explain {
-                        println!(
-                            "Photograph Joker {:?}{:?} x2 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            Joker::SmileyFace => {
-                if played_card.is_face {
-                    curr_mult += 5.0;
-                    if *explain {
-                        println!(
-                            "Smiley Face Joker {:?}{:?} +5 Mult ( {:?} x {:?} )",
-                            played_card.rank,
-                            played_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
-                }
-            }
-            _ => {}
+        match data.joker_effect(self.joker) {
+            Some(effect) => effect.on_scored_card(current_chips, current_mult, played_card, data),
+            None => (*current_chips, *current_mult),
         }
-        return (curr_chips, curr_mult);
     }
 
     fn calculate_on_held_cards(
         &self,
-        explain: &bool,
         current_chips: &Chips,
         current_mult: &Mult,
         held_card: &HandCard,
         data: &ScoringData
     ) -> (Chips, Mult) {
-        let curr_chips: Chips = *current_chips;
-        let mut curr_mult: Mult = *current_mult;
-        let cards_in_hand = &data.hand_cards;
-
-        match self.joker {
-            Joker::RaisedFist => {
-                let smallest_card = cards_in_hand
-                    .iter()
-                    .enumerate() // Enumerate to get the index
-                    .rev() // Reverse the order
-                    .min_by_key(|(_, card)| card.rank.rank_value() as u8); // Get the smallest card
-
-                if let Some((index, card)) = smallest_card {
-                    if index == held_card.hand_card_index {
-                        let increase_value = 2.0 * card.rank.rank_value();
-                        curr_mult += increase_value;
-                        if *explain {
-                            println!(
-                                "Raised Fist Joker {:?}{:?} + {:?} Mult ( {:?} x {:?} )",
-                                card.rank,
-                                card.suit,
-                                increase_value,
-                                curr_chips,
-                                curr_mult
-                            );
-                        }
-                    }
-                }
-            }
-            Joker::Baron => {
-                if held_card.rank == Rank::King {
-                    curr_mult *= 1.5;
-                    if *explain {
-                        println!(
-                            "Baron Joker {:?}{:?} 1.5x Mult ( {:?} x {:?} )",
-                            held_card.rank,
-                            held_card.suit,
-                            curr_chips,
-                            curr_mult
-                        );
-                    }
+        // Every held card joker depends on rank, which stone cards lack
+        if held_card.enhancement == Some(Enhancement::Stone) {
+            return (*current_chips, *current_mult);
+        }
+
+        match data.joker_effect(self.joker) {
+            Some(effect) => effect.on_held_card(current_chips, current_mult, held_card, data),
+            None => (*current_chips, *current_mult),
+        }
+    }
+}
+
+impl EvaluationRules {
+    /// Rules once every joker has changed them, in order
+    pub fn for_jokers(jokers: &[JokerCard], data: &ScoringData) -> EvaluationRules {
+        let mut rules = EvaluationRules::default();
+        jokers
+            .iter()
+            .filter_map(|joker| data.joker_effect(joker.joker))
+            .for_each(|effect| effect.modify_evaluation(&mut rules));
+        rules
+    }
+}
+
+impl JokerRegistry {
+    /// Registry with the effect of every built-in joker
+    pub fn builtin() -> JokerRegistry {
+        use PokerHand::*;
+
+        let mut registry = JokerRegistry::default();
+        registry.register(Joker::Joker, PlainJoker);
+        registry.register(Joker::JollyJoker, HandMult::new(Joker::JollyJoker, Pair, 8.0));
+        registry.register(Joker::ZanyJoker, HandMult::new(Joker::ZanyJoker, ThreeOfAKind, 12.0));
+        registry.register(Joker::MadJoker, HandMult::new(Joker::MadJoker, TwoPair, 10.0));
+        registry.register(Joker::CrazyJoker, HandMult::new(Joker::CrazyJoker, Straight, 12.0));
+        registry.register(Joker::DrollJoker, HandMult::new(Joker::DrollJoker, Flush, 10.0));
+        registry.register(Joker::SlyJoker, HandChips::new(Joker::SlyJoker, Pair, 50.0));
+        registry.register(Joker::WilyJoker, HandChips::new(Joker::WilyJoker, ThreeOfAKind, 100.0));
+        registry.register(Joker::CleverJoker, HandChips::new(Joker::CleverJoker, TwoPair, 80.0));
+        registry.register(
+            Joker::DeviousJoker,
+            HandChips::new(Joker::DeviousJoker, Straight, 100.0)
+        );
+        registry.register(Joker::CraftyJoker, HandChips::new(Joker::CraftyJoker, Flush, 80.0));
+        registry.register(Joker::AbstractJoker, AbstractJoker);
+        registry.register(Joker::RaisedFist, RaisedFist);
+        registry.register(Joker::Blackboard, Blackboard);
+        registry.register(Joker::Baron, Baron);
+        registry.register(Joker::GreedyJoker, SuitMult::new(Joker::GreedyJoker, Suit::Diamonds));
+        registry.register(Joker::LustyJoker, SuitMult::new(Joker::LustyJoker, Suit::Hearts));
+        registry.register(Joker::WrathfulJoker, SuitMult::new(Joker::WrathfulJoker, Suit::Spades));
+        registry.register(
+            Joker::GluttonousJoker,
+            SuitMult::new(Joker::GluttonousJoker, Suit::Clubs)
+        );
+        registry.register(Joker::Fibonacci, Fibonacci);
+        registry.register(Joker::ScaryFace, ScaryFace);
+        registry.register(Joker::EvenSteven, EvenSteven);
+        registry.register(Joker::OddTodd, OddTodd);
+        registry.register(Joker::Photograph, Photograph);
+        registry.register(Joker::SmileyFace, SmileyFace);
+        registry.register(Joker::FlowerPot, FlowerPot);
+        registry.register(Joker::FourFingers, FourFingers);
+        registry.register(Joker::Shortcut, Shortcut);
+        registry.register(Joker::Mime, Mime);
+        registry.register(Joker::Pareidolia, Pareidolia);
+        registry.register(Joker::Splash, Splash);
+        registry.register(Joker::SockAndBuskin, SockAndBuskin);
+        registry.register(Joker::SmearedJoker, SmearedJoker);
+        // A Blueprint with nothing to copy scores like Crafty Joker
+        registry.register(Joker::Blueprint, HandChips::new(Joker::Blueprint, Flush, 80.0));
+        registry.register(Joker::Misprint, Misprint);
+        registry.register(Joker::Bloodstone, Bloodstone);
+        registry.register(Joker::SpaceJoker, SpaceJoker);
+        registry.register(Joker::RideTheBus, RideTheBus);
+        registry.register(Joker::GreenJoker, GreenJoker);
+        registry.register(Joker::IceCream, IceCream);
+        registry.register(Joker::Runner, Runner);
+        registry.register(Joker::SquareJoker, SquareJoker);
+        registry.register(Joker::SpareTrousers, SpareTrousers);
+        registry.register(Joker::Hiker, Hiker);
+        registry.register(Joker::WeeJoker, WeeJoker);
+        registry.register(Joker::Supernova, Supernova);
+        registry.register(Joker::CardSharp, CardSharp);
+        registry.register(Joker::Obelisk, Obelisk);
+        registry.register(Joker::LoyaltyCard, LoyaltyCard);
+        registry.register(Joker::Banner, Banner);
+        registry.register(Joker::MysticSummit, MysticSummit);
+        registry.register(Joker::HalfJoker, HalfJoker);
+        registry.register(Joker::Acrobat, Acrobat);
+        registry.register(Joker::Dusk, Dusk);
+        registry
+    }
+
+    /// Sets the effect of a joker, replacing the effect it already had
+    pub fn register(&mut self, joker: Joker, effect: impl JokerEffect + 'static) {
+        self.effects.insert(joker, Arc::new(effect));
+    }
+
+    pub fn get(&self, joker: Joker) -> Option<Arc<dyn JokerEffect>> {
+        self.effects.get(&joker).cloned()
+    }
+}
+
+/// Sets the effect of a joker for all later scoring, replacing the effect it already had
+pub fn register_joker(joker: Joker, effect: impl JokerEffect + 'static) {
+    REGISTRY.write().unwrap_or_else(|err| err.into_inner()).register(joker, effect);
+}
+
+/// Effect of a joker, `None` when the joker has no effect registered
+pub fn joker_effect(joker: Joker) -> Option<Arc<dyn JokerEffect>> {
+    REGISTRY.read().unwrap_or_else(|err| err.into_inner()).get(joker)
+}
+
+/// Registered custom joker with the given name
+pub fn custom_joker(name: &str) -> Option<Joker> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .effects
+        .keys()
+        .find(|joker| matches!(joker, Joker::Custom(custom) if *custom == name))
+        .copied()
+}
+
+/// Adds chips and records the step
+fn add_chips(
+    source: EventSource,
+    chips: f64,
+    current_chips: &Chips,
+    current_mult: &Mult,
+    data: &ScoringData
+) -> (Chips, Mult) {
+    let curr_chips = *current_chips + chips;
+    data.record(ScoreEvent::new(source, EventKind::AddChips, chips, curr_chips, *current_mult));
+    (curr_chips, *current_mult)
+}
+
+/// Adds mult and records the step
+fn add_mult(
+    source: EventSource,
+    mult: f64,
+    current_chips: &Chips,
+    current_mult: &Mult,
+    data: &ScoringData
+) -> (Chips, Mult) {
+    let curr_mult = *current_mult + mult;
+    data.record(ScoreEvent::new(source, EventKind::AddMult, mult, *current_chips, curr_mult));
+    (*current_chips, curr_mult)
+}
+
+/// Multiplies mult and records the step
+fn x_mult(
+    source: EventSource,
+    mult: f64,
+    current_chips: &Chips,
+    current_mult: &Mult,
+    data: &ScoringData
+) -> (Chips, Mult) {
+    let curr_mult = *current_mult * mult;
+    data.record(ScoreEvent::new(source, EventKind::XMult, mult, *current_chips, curr_mult));
+    (*current_chips, curr_mult)
+}
+
+/// Adds the chips and mult a scaling joker has built up, recording each step
+fn add_state(
+    joker: Joker,
+    current_chips: &Chips,
+    current_mult: &Mult,
+    data: &ScoringData
+) -> (Chips, Mult) {
+    let state = data.joker_states.borrow().joker(joker);
+    let (mut chips, mut mult) = (*current_chips, *current_mult);
+    if state.chips != 0.0 {
+        (chips, mult) = add_chips(EventSource::joker(joker), state.chips, &chips, &mult, data);
+    }
+    if state.mult != 0.0 {
+        (chips, mult) = add_mult(EventSource::joker(joker), state.mult, &chips, &mult, data);
+    }
+    (chips, mult)
+}
+
+/// Scored cards which are not debuffed by the boss blind
+fn live_scored_cards<'a>(data: &'a ScoringData) -> impl Iterator<Item = &'a ScoringPlayedCard> {
+    data.scored_cards.iter().filter(|card| !card.debuffed)
+}
+
+/// Whether the card counts as the suit, Wild cards are every suit
+fn has_suit(card: &ScoringPlayedCard, suit: Suit, smeared: bool) -> bool {
+    card.suit == suit ||
+        card.enhancement == Some(Enhancement::Wild) ||
+        (smeared && card.suit.color() == suit.color())
+}
+
+/// +4 Mult
+struct PlainJoker;
+
+/// +Mult if the played hand contains a poker hand, such as Jolly Joker
+struct HandMult {
+    joker: Joker,
+    hand: PokerHand,
+    mult: f64,
+}
+
+/// +Chips if the played hand contains a poker hand, such as Sly Joker
+struct HandChips {
+    joker: Joker,
+    hand: PokerHand,
+    chips: f64,
+}
+
+/// +3 Mult for each joker
+struct AbstractJoker;
+
+/// Adds double the rank of the lowest ranked card held in hand to Mult
+struct RaisedFist;
+
+/// x3 Mult if every card held in hand is a Spade or Club
+struct Blackboard;
+
+/// Each King held in hand gives x1.5 Mult
+struct Baron;
+
+/// Played cards of a suit give +3 Mult when scored, such as Greedy Joker
+struct SuitMult {
+    joker: Joker,
+    suit: Suit,
+}
+
+/// Each played Ace, 2, 3, 5 or 8 gives +8 Mult when scored
+struct Fibonacci;
+
+/// Played face cards give +30 Chips when scored
+struct ScaryFace;
+
+/// Played cards with an even rank give +4 Mult when scored
+struct EvenSteven;
+
+/// Played cards with an odd rank give +31 Chips when scored
+struct OddTodd;
+
+/// The first played face card gives x2 Mult when scored
+struct Photograph;
+
+/// Played face cards give +5 Mult when scored
+struct SmileyFace;
+
+/// x3 Mult if the scoring cards include a Diamond, Club, Heart and Spade
+struct FlowerPot;
+
+/// Flushes and Straights can be made with 4 cards
+struct FourFingers;
+
+/// Straights can be made with gaps of 1 rank
+struct Shortcut;
+
+/// Retriggers every card held in hand
+struct Mime;
+
+/// Every card is a face card
+struct Pareidolia;
+
+/// Every played card scores
+struct Splash;
+
+/// Retriggers every played face card
+struct SockAndBuskin;
+
+/// Hearts and Diamonds count as the same suit, as do Spades and Clubs
+struct SmearedJoker;
+
+/// +0 to +23 Mult
+struct Misprint;
+
+/// 1 in 2 chance for played Hearts to give x1.5 Mult when scored
+struct Bloodstone;
+
+/// 1 in 4 chance to upgrade the level of the played poker hand
+struct SpaceJoker;
+
+/// Gains +1 Mult per consecutive hand played without a scoring face card
+struct RideTheBus;
+
+/// Gains +1 Mult per hand played and loses 1 Mult per discard
+struct GreenJoker;
+
+/// +100 Chips, losing 5 Chips for every hand played
+struct IceCream;
+
+/// Gains +15 Chips if the played hand contains a Straight
+struct Runner;
+
+/// Gains +4 Chips if the played hand has exactly 4 cards
+struct SquareJoker;
+
+/// Gains +2 Mult if the played hand contains a Two Pair
+struct SpareTrousers;
+
+/// Every played card permanently gains +5 Chips each time it scores
+struct Hiker;
+
+/// Gains +8 Chips each time a played 2 scores
+struct WeeJoker;
+
+/// +Mult for each time the played poker hand has been played this run, this hand included
+struct Supernova;
+
+/// x3 Mult if the played poker hand was already played this round
+struct CardSharp;
+
+/// x0.2 Mult for each hand in a row without playing the most played poker hand
+struct Obelisk;
+
+/// x4 Mult on every 6th hand of the run
+struct LoyaltyCard;
+
+/// +30 Chips for each discard left
+struct Banner;
+
+/// +15 Mult when no discards are left
+struct MysticSummit;
+
+/// +20 Mult if the played hand has at most 3 cards
+struct HalfJoker;
+
+/// x3 Mult on the final hand of the round
+struct Acrobat;
+
+/// Retriggers every played card on the final hand of the round
+struct Dusk;
+
+impl HandMult {
+    fn new(joker: Joker, hand: PokerHand, mult: f64) -> HandMult {
+        HandMult { joker, hand, mult }
+    }
+}
+
+impl HandChips {
+    fn new(joker: Joker, hand: PokerHand, chips: f64) -> HandChips {
+        HandChips { joker, hand, chips }
+    }
+}
+
+impl SuitMult {
+    fn new(joker: Joker, suit: Suit) -> SuitMult {
+        SuitMult { joker, suit }
+    }
+}
+
+impl JokerEffect for PlainJoker {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_mult(EventSource::joker(Joker::Joker), 4.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for HandMult {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !data.pokerhands.contains(&self.hand) {
+            return (*current_chips, *current_mult);
+        }
+        add_mult(EventSource::joker(self.joker), self.mult, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for HandChips {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !data.pokerhands.contains(&self.hand) {
+            return (*current_chips, *current_mult);
+        }
+        add_chips(EventSource::joker(self.joker), self.chips, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for AbstractJoker {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let increase_value = 3.0 * (data.joker_cards.len() as f64);
+        let source = EventSource::joker(Joker::AbstractJoker);
+        add_mult(source, increase_value, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Blackboard {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        // Stone cards have no suit so they are never Spades or Clubs
+        let contains_red_suit = data.hand_cards
+            .iter()
+            .any(|card| {
+                card.enhancement == Some(Enhancement::Stone) ||
+                    ((card.suit == Suit::Diamonds || card.suit == Suit::Hearts) &&
+                        card.enhancement != Some(Enhancement::Wild))
+            });
+
+        if contains_red_suit {
+            return (*current_chips, *current_mult);
+        }
+        x_mult(EventSource::joker(Joker::Blackboard), 3.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for FlowerPot {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let is_smeared = data.rules.smeared;
+        let mut num_wild: u8 = 0;
+        let mut suit_count: HashMap<Suit, u8> = HashMap::new();
+        let mut colour_count: HashMap<SuitColor, u8> = HashMap::new();
+        data.scored_cards
+            .iter()
+            .filter(|card| card.enhancement != Some(Enhancement::Stone))
+            .for_each(|card| {
+                if card.enhancement == Some(Enhancement::Wild) {
+                    num_wild += 1;
+                    *suit_count.entry(Suit::Clubs).or_insert(0) += 1;
+                    *suit_count.entry(Suit::Diamonds).or_insert(0) += 1;
+                    *suit_count.entry(Suit::Spades).or_insert(0) += 1;
+                    *suit_count.entry(Suit::Hearts).or_insert(0) += 1;
+                    *colour_count.entry(SuitColor::Black).or_insert(0) += 1;
+                    *colour_count.entry(SuitColor::Red).or_insert(0) += 1;
+                } else {
+                    *suit_count.entry(card.suit).or_insert(0) += 1;
+                    *colour_count.entry(card.suit.color()).or_insert(0) += 1;
                 }
-            }
-            _ => {}
+            });
+        let is_flower_pot = if is_smeared && num_wild == 0 {
+            // Check for atleast 2 Reds and 2 Blacks
+            colour_count
+                .values()
+                .filter(|&&count| count >= 2)
+                .count() == 2
+        } else if is_smeared {
+            num_wild >= 4 ||
+                (num_wild == 3 && colour_count.values().any(|&value| value > 4)) ||
+                (num_wild == 2 &&
+                    (colour_count
+                        .values()
+                        .filter(|&&value| value >= 3)
+                        .count() == 2 ||
+                        colour_count.values().any(|&value| value >= 4))) ||
+                (num_wild == 1 &&
+                    colour_count.values().any(|&value| value >= 3) &&
+                    colour_count
+                        .values()
+                        .filter(|&&value| value >= 2)
+                        .count() == 2)
+        } else if num_wild == 0 {
+            suit_count.len() == 4
+        } else {
+            num_wild >= 4 ||
+                (num_wild == 3 && suit_count.values().any(|&value| value > 3)) ||
+                (num_wild == 2 &&
+                    suit_count
+                        .values()
+                        .filter(|&&value| value > 2)
+                        .count() >= 2) ||
+                (num_wild == 1 &&
+                    suit_count
+                        .values()
+                        .filter(|&&value| value > 1)
+                        .count() >= 3)
+        };
+
+        if !is_flower_pot {
+            return (*current_chips, *current_mult);
         }
-        return (curr_chips, curr_mult);
+        x_mult(EventSource::joker(Joker::FlowerPot), 3.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Misprint {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let increase_value = f64::from(data.roll(24));
+        let source = EventSource::joker(Joker::Misprint);
+        add_mult(source, increase_value, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for SuitMult {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !has_suit(card, self.suit, data.rules.smeared) {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(self.joker, card.rank, card.suit);
+        add_mult(source, 3.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Fibonacci {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !matches!(card.rank, Rank::Ace | Rank::Two | Rank::Three | Rank::Five | Rank::Eight) {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::Fibonacci, card.rank, card.suit);
+        add_mult(source, 8.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for ScaryFace {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !card.is_face {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::ScaryFace, card.rank, card.suit);
+        add_chips(source, 30.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for EvenSteven {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if card.rank.is_face() || card.rank.rank_value() % 2.0 != 0.0 {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::EvenSteven, card.rank, card.suit);
+        add_mult(source, 4.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for OddTodd {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if card.rank.is_face() || card.rank.rank_value() % 2.0 != 1.0 {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::OddTodd, card.rank, card.suit);
+        add_chips(source, 31.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Photograph {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let first_face_card_index = data.scored_cards.iter().position(|card| card.is_face);
+        if first_face_card_index != Some(card.scored_card_index) {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::Photograph, card.rank, card.suit);
+        x_mult(source, 2.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for SmileyFace {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !card.is_face {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::SmileyFace, card.rank, card.suit);
+        add_mult(source, 5.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Bloodstone {
+    fn on_scored_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        card: &ScoringPlayedCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        // Only Hearts roll, so other cards leave the random rolls untouched
+        if !has_suit(card, Suit::Hearts, data.rules.smeared) || !data.chance(1, 2) {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::Bloodstone, card.rank, card.suit);
+        x_mult(source, 1.5, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for SpaceJoker {
+    fn modify_hand_levels(&self, hand: PokerHand, levels: &mut HandLevels, data: &ScoringData) {
+        if data.chance(1, 4) {
+            levels.level_up(hand);
+        }
+    }
+}
+
+impl JokerEffect for RaisedFist {
+    fn on_held_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        held_card: &HandCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let smallest_card = data.hand_cards
+            .iter()
+            .enumerate() // Enumerate to get the index
+            .filter(|(_, card)| card.enhancement != Some(Enhancement::Stone))
+            .rev() // Reverse the order
+            .min_by_key(|(_, card)| card.rank.rank_value() as u8); // Get the smallest card
+
+        match smallest_card {
+            Some((index, card)) if index == held_card.hand_card_index => {
+                let increase_value = 2.0 * card.rank.rank_value();
+                let source = EventSource::joker_on(Joker::RaisedFist, card.rank, card.suit);
+                add_mult(source, increase_value, current_chips, current_mult, data)
+            }
+            _ => (*current_chips, *current_mult),
+        }
+    }
+}
+
+impl JokerEffect for Baron {
+    fn on_held_card(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        held_card: &HandCard,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if held_card.rank != Rank::King {
+            return (*current_chips, *current_mult);
+        }
+        let source = EventSource::joker_on(Joker::Baron, held_card.rank, held_card.suit);
+        x_mult(source, 1.5, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for FourFingers {
+    fn modify_evaluation(&self, rules: &mut EvaluationRules) {
+        rules.four_fingers = true;
+    }
+}
+
+impl JokerEffect for Shortcut {
+    fn modify_evaluation(&self, rules: &mut EvaluationRules) {
+        rules.shortcut = true;
+    }
+}
+
+impl JokerEffect for SmearedJoker {
+    fn modify_evaluation(&self, rules: &mut EvaluationRules) {
+        rules.smeared = true;
+    }
+}
+
+impl JokerEffect for Pareidolia {
+    fn modify_evaluation(&self, rules: &mut EvaluationRules) {
+        rules.all_faces = true;
+    }
+
+    fn setup(&self, data: &mut ScoringData) {
+        // Stone cards have no rank so they cannot be face cards
+        data.scored_cards
+            .iter_mut()
+            .filter(|card| card.enhancement != Some(Enhancement::Stone))
+            .for_each(|card| {
+                card.is_face = true;
+            });
+    }
+}
+
+impl JokerEffect for Splash {
+    fn modify_evaluation(&self, rules: &mut EvaluationRules) {
+        rules.every_card_scores = true;
+    }
+}
+
+impl JokerEffect for Mime {
+    fn retrigger_count(&self, card: RetriggeredCard, _data: &ScoringData) -> u32 {
+        match card {
+            RetriggeredCard::Held(_) => 1,
+            RetriggeredCard::Scored(_) => 0,
+        }
+    }
+}
+
+impl JokerEffect for SockAndBuskin {
+    fn retrigger_count(&self, card: RetriggeredCard, _data: &ScoringData) -> u32 {
+        match card {
+            RetriggeredCard::Scored(card) if card.is_face => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl JokerEffect for RideTheBus {
+    fn before_scoring(&self, states: &mut JokerStates, data: &ScoringData) {
+        let state = states.joker_mut(Joker::RideTheBus);
+        if live_scored_cards(data).any(|card| card.is_face) {
+            state.mult = 0.0;
+        } else {
+            state.mult += 1.0;
+        }
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_state(Joker::RideTheBus, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for GreenJoker {
+    fn before_scoring(&self, states: &mut JokerStates, _data: &ScoringData) {
+        states.joker_mut(Joker::GreenJoker).mult += 1.0;
+    }
+
+    fn on_discard(&self, states: &mut JokerStates, _cards: &[PlayingCard]) {
+        let state = states.joker_mut(Joker::GreenJoker);
+        state.mult = (state.mult - 1.0).max(0.0);
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_state(Joker::GreenJoker, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for IceCream {
+    fn initial_state(&self) -> JokerState {
+        JokerState { chips: 100.0, ..JokerState::default() }
+    }
+
+    fn after_scoring(&self, states: &mut JokerStates, _data: &ScoringData) {
+        let state = states.joker_mut(Joker::IceCream);
+        state.chips = (state.chips - 5.0).max(0.0);
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_state(Joker::IceCream, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Runner {
+    fn before_scoring(&self, states: &mut JokerStates, data: &ScoringData) {
+        if data.pokerhands.contains(&PokerHand::Straight) {
+            states.joker_mut(Joker::Runner).chips += 15.0;
+        }
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_state(Joker::Runner, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for SquareJoker {
+    fn before_scoring(&self, states: &mut JokerStates, data: &ScoringData) {
+        if data.played_cards.len() == 4 {
+            states.joker_mut(Joker::SquareJoker).chips += 4.0;
+        }
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_state(Joker::SquareJoker, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for SpareTrousers {
+    fn before_scoring(&self, states: &mut JokerStates, data: &ScoringData) {
+        if data.pokerhands.contains(&PokerHand::TwoPair) {
+            states.joker_mut(Joker::SpareTrousers).mult += 2.0;
+        }
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_state(Joker::SpareTrousers, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Hiker {
+    // The chips stay with the card, `ScoringPlayedCard::add_scores` adds them from its next
+    // trigger on
+    fn on_scored_card_state(
+        &self,
+        states: &mut JokerStates,
+        card: &ScoringPlayedCard,
+        _data: &ScoringData
+    ) {
+        states.add_card_chips(card.card(), 5.0);
+    }
+}
+
+impl JokerEffect for WeeJoker {
+    fn on_scored_card_state(
+        &self,
+        states: &mut JokerStates,
+        card: &ScoringPlayedCard,
+        _data: &ScoringData
+    ) {
+        if card.rank == Rank::Two {
+            states.joker_mut(Joker::WeeJoker).chips += 8.0;
+        }
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        add_state(Joker::WeeJoker, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Supernova {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let plays = f64::from(data.round.run_history.play_count(data.pokerhands[0]) + 1);
+        add_mult(EventSource::joker(Joker::Supernova), plays, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for CardSharp {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !data.round.history.hands_played_this_round.contains(&data.pokerhands[0]) {
+            return (*current_chips, *current_mult);
+        }
+        x_mult(EventSource::joker(Joker::CardSharp), 3.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Obelisk {
+    // Counts the hands in a row, this one included, which were not the most played poker hand
+    // when they were played
+    fn before_scoring(&self, states: &mut JokerStates, data: &ScoringData) {
+        let mut counts = data.round.run_history.clone();
+        counts.record(data.pokerhands[0]);
+
+        let state = states.joker_mut(Joker::Obelisk);
+        if counts.is_most_played(data.pokerhands[0]) {
+            state.streak = 0;
+        } else {
+            state.streak += 1;
+        }
+    }
+
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let streak = data.joker_states.borrow().joker(Joker::Obelisk).streak;
+        if streak == 0 {
+            return (*current_chips, *current_mult);
+        }
+        let mult = 1.0 + 0.2 * f64::from(streak);
+        x_mult(EventSource::joker(Joker::Obelisk), mult, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for LoyaltyCard {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !(data.round.run_history.hands_played() + 1).is_multiple_of(6) {
+            return (*current_chips, *current_mult);
+        }
+        x_mult(EventSource::joker(Joker::LoyaltyCard), 4.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Banner {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        let discards = data.round.context().discards_left;
+        if discards == 0 {
+            return (*current_chips, *current_mult);
+        }
+        let chips = 30.0 * f64::from(discards);
+        add_chips(EventSource::joker(Joker::Banner), chips, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for MysticSummit {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if data.round.context().discards_left != 0 {
+            return (*current_chips, *current_mult);
+        }
+        add_mult(EventSource::joker(Joker::MysticSummit), 15.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for HalfJoker {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if data.played_cards.len() > 3 {
+            return (*current_chips, *current_mult);
+        }
+        add_mult(EventSource::joker(Joker::HalfJoker), 20.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Acrobat {
+    fn independent(
+        &self,
+        current_chips: &Chips,
+        current_mult: &Mult,
+        data: &ScoringData
+    ) -> (Chips, Mult) {
+        if !data.round.context().is_final_hand() {
+            return (*current_chips, *current_mult);
+        }
+        x_mult(EventSource::joker(Joker::Acrobat), 3.0, current_chips, current_mult, data)
+    }
+}
+
+impl JokerEffect for Dusk {
+    fn retrigger_count(&self, card: RetriggeredCard, data: &ScoringData) -> u32 {
+        let final_hand = data.round.context().is_final_hand();
+        u32::from(matches!(card, RetriggeredCard::Scored(_)) && final_hand)
     }
 }
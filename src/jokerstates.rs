@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::fmt::{ Display, Formatter };
+
+use ortalib::{ Chips, Mult };
+use serde::{ Deserialize, Serialize };
+
+use crate::cards::{ Joker, JokerCard, PlayingCard };
+use crate::jokers::joker_effect;
+
+/// Chips and mult a scaling joker has built up over earlier hands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JokerState {
+    pub chips: Chips,
+    pub mult: Mult,
+    /// Hands in a row counted by jokers such as Obelisk
+    #[serde(skip_serializing_if = "is_zero")]
+    pub streak: u32,
+}
+
+/// What scaling jokers carry from one hand to the next
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(try_from = "JokerStatesInput")]
+pub struct JokerStates {
+    jokers: HashMap<Joker, JokerState>,
+    /// Chips cards have gained from Hiker, keyed by the whole card so copies of a rank and
+    /// suit with a different enhancement, edition or seal keep their own
+    card_chips: HashMap<PlayingCard, Chips>,
+}
+
+/// Joker states as written in the input, such as `jokers: { Green Joker: { mult: 3 } }`,
+/// `jokers: { Obelisk: { streak: 2 } }` and `card_chips: { K♥ Glass: 10 }`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JokerStatesInput {
+    #[serde(default)]
+    jokers: HashMap<String, JokerState>,
+    #[serde(default)]
+    card_chips: HashMap<String, Chips>,
+}
+
+impl JokerStates {
+    /// State of a joker, the joker's starting state if it has not built any up yet
+    pub fn joker(&self, joker: Joker) -> JokerState {
+        self.jokers.get(&joker).copied().unwrap_or_else(|| initial_state(joker))
+    }
+
+    pub fn joker_mut(&mut self, joker: Joker) -> &mut JokerState {
+        self.jokers.entry(joker).or_insert_with(|| initial_state(joker))
+    }
+
+    /// Chips the card has gained on top of its rank
+    pub fn card_chips(&self, card: &PlayingCard) -> Chips {
+        self.card_chips.get(card).copied().unwrap_or(0.0)
+    }
+
+    pub fn add_card_chips(&mut self, card: PlayingCard, chips: Chips) {
+        *self.card_chips.entry(card).or_insert(0.0) += chips;
+    }
+
+    /// Updates the jokers for cards discarded between hands
+    pub fn discard(&mut self, jokers: &[JokerCard], cards: &[PlayingCard]) {
+        distinct_jokers(jokers).into_iter().for_each(|joker| {
+            if let Some(effect) = joker_effect(joker) {
+                effect.on_discard(self, cards);
+            }
+        });
+    }
+
+    /// States of the given jokers which have built anything up, in joker order
+    pub fn summary(&self, jokers: &[JokerCard]) -> Vec<(Joker, JokerState)> {
+        distinct_jokers(jokers)
+            .into_iter()
+            .filter_map(|joker| self.jokers.get(&joker).map(|state| (joker, *state)))
+            .collect()
+    }
+
+    /// Chips gained by cards, as `K♥` and the chips, ordered by suit then rank
+    pub fn card_summary(&self) -> Vec<(String, Chips)> {
+        let mut cards: Vec<(&PlayingCard, &Chips)> = self.card_chips.iter().collect();
+        cards.sort_by_key(|(card, _)| (card.suit, card.rank, card.to_string()));
+        cards
+            .into_iter()
+            .map(|(card, &chips)| (card.to_string(), chips))
+            .collect()
+    }
+}
+
+/// Each joker once, in order, since copies of a joker share its state
+pub fn distinct_jokers(jokers: &[JokerCard]) -> Vec<Joker> {
+    let mut distinct: Vec<Joker> = Vec::new();
+    jokers.iter().for_each(|joker| {
+        if !distinct.contains(&joker.joker) {
+            distinct.push(joker.joker);
+        }
+    });
+    distinct
+}
+
+fn initial_state(joker: Joker) -> JokerState {
+    joker_effect(joker).map_or_else(JokerState::default, |effect| effect.initial_state())
+}
+
+impl TryFrom<JokerStatesInput> for JokerStates {
+    type Error = String;
+
+    fn try_from(input: JokerStatesInput) -> Result<Self, Self::Error> {
+        let mut states = JokerStates::default();
+        for (name, state) in input.jokers {
+            states.jokers.insert(name.parse()?, state);
+        }
+        for (card, chips) in input.card_chips {
+            states.add_card_chips(card.parse()?, chips);
+        }
+        Ok(states)
+    }
+}
+
+fn is_zero(streak: &u32) -> bool {
+    *streak == 0
+}
+
+impl Display for JokerState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.chips != 0.0, self.mult != 0.0) {
+            (true, true) => write!(f, "+{} Chips +{} Mult", self.chips, self.mult)?,
+            (true, false) => write!(f, "+{} Chips", self.chips)?,
+            (false, true) => write!(f, "+{} Mult", self.mult)?,
+            (false, false) if self.streak > 0 => return write!(f, "{} in a row", self.streak),
+            (false, false) => write!(f, "+0")?,
+        }
+        if self.streak > 0 {
+            write!(f, ", {} in a row", self.streak)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! Scoring engine for Ortalab rounds.
+//!
+//! The engine never writes to stdout, callers decide what to do with the
+//! returned `ScoreReport`.
+
+use ortalib::Round;
+
+use consumables::apply_consumables;
+
+pub mod roundscorer;
+pub mod cardscorer;
+pub mod utils;
+pub mod pokerservice;
+pub mod jokers;
+pub mod modifiers;
+pub mod events;
+pub mod explain;
+pub mod input;
+pub mod handlevels;
+pub mod jokerstates;
+pub mod consumables;
+pub mod blinds;
+pub mod target;
+pub mod cards;
+pub mod effects;
+pub mod rng;
+pub mod simulate;
+pub mod distribution;
+pub mod optimize;
+pub mod discard;
+pub mod deck;
+pub mod run;
+pub mod shop;
+pub mod custom_jokers;
+pub mod custom_hands;
+pub mod sequence;
+
+pub use roundscorer::{ RoundScorer, ScoreOptions, ScoreReport };
+pub use events::{ EventKind, EventSource, ScoreEvent };
+pub use custom_hands::{ CustomHand, HandRule, HandType, register_hand };
+pub use explain::ExplainDocument;
+pub use input::RoundInput;
+pub use jokers::{ EvaluationRules, JokerEffect, JokerRegistry, register_joker };
+pub use target::TargetOutcome;
+pub use rng::{ GameRng, SeededRng };
+pub use simulate::{ SimulationReport, simulate };
+pub use distribution::{ ScoreDistribution, exact_distribution };
+pub use deck::{ Deck, DeckReport };
+pub use sequence::{ HandSequence, SequenceReport, score_sequence };
+pub use run::{ Run, RunCommand, RunConfig, RunEvent, RunState, RunStatus };
+pub use discard::{ DiscardAdvice, DiscardCandidate, advise_discard };
+pub use optimize::{
+    JokerOrder,
+    JokerOrderReport,
+    OptimizeReport,
+    Play,
+    optimize,
+    optimize_joker_order,
+};
+
+/// Scores a single round, returning the final chips, mult, score and the hand played
+pub fn score_round(round: &Round, options: &ScoreOptions) -> ScoreReport {
+    score_input(&RoundInput::from(round), options)
+}
+
+/// Scores a round along with its hand levels, consumables and blind
+pub fn score_input(round: &RoundInput, options: &ScoreOptions) -> ScoreReport {
+    score_with_rng(round, options, &mut SeededRng::new(options.seed))
+}
+
+/// Scores a round, drawing random effects from the given generator instead of the seed
+pub fn score_with_rng(
+    round: &RoundInput,
+    options: &ScoreOptions,
+    rng: &mut dyn GameRng
+) -> ScoreReport {
+    // Tarots and spectrals change the cards before anything is scored. Their random choices
+    // come from the seed so every roll of the scoring rng starts from the same cards.
+    let used = apply_consumables(round, &mut SeededRng::new(options.seed));
+    let round = used.as_ref().map_or(round, |used| &used.round);
+
+    let mut scorer = RoundScorer::new(round, rng);
+    let (chips, mult) = scorer.get_score(options.explain);
+
+    // A target given in the options overrides the one in the round
+    let target = options.target.or_else(|| round.blind_target());
+
+    let mut report = scorer.into_report();
+    report.outcome = target.map(|target| TargetOutcome::new(chips, mult, target));
+    if let Some(used) = used {
+        report.effects.money += used.money;
+        report.card_changes = used.changes;
+    }
+    report
+}
@@ -1,49 +1,389 @@
-use std::{ error::Error, fs::File, io::{ Read, stdin }, path::{ Path, PathBuf } };
+use std::{
+    error::Error,
+    fs::File,
+    io::{ Read, stdin },
+    path::{ Path, PathBuf },
+    process::ExitCode,
+};
 
-use clap::Parser;
-use ortalib::{ Chips, Mult, Round };
-use roundscorer::RoundScorer;
-
-mod roundscorer;
-mod cardscorer;
-mod utils;
-mod pokerservice;
-mod jokers;
-mod modifiers;
+use clap::{ Parser, Subcommand, ValueEnum };
+use ortalab::consumables::check_consumable_targets;
+use ortalab::custom_hands::register_custom_hands;
+use ortalab::custom_jokers::register_custom_jokers;
+use ortalab::{
+    Deck,
+    ExplainDocument,
+    HandSequence,
+    RoundInput,
+    ScoreOptions,
+    RunCommand,
+    RunConfig,
+    RunEvent,
+    RunStatus,
+    Run,
+    ScoreReport,
+    advise_discard,
+    exact_distribution,
+    optimize,
+    optimize_joker_order,
+    optimize::DEFAULT_MAX_ORDERS,
+    score_input,
+    score_sequence,
+    simulate,
+};
 
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Opts {
-    file: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(required = true)]
+    file: Option<PathBuf>,
 
     #[arg(long)]
     explain: bool,
+
+    /// Output format of the explanation, implies --explain
+    #[arg(long, value_enum, global = true)]
+    explain_format: Option<ExplainFormat>,
+
+    /// Score needed to beat the blind, exits with status 2 when the hand falls short
+    #[arg(long, global = true)]
+    target: Option<f64>,
+
+    /// Seed for random effects such as Lucky cards, the same seed gives the same score
+    #[arg(long, default_value_t = 0, global = true)]
+    seed: u64,
+
+    /// Definitions file of custom hands, which played cards can make and custom jokers can
+    /// refer to by name
+    #[arg(long, value_name = "FILE", global = true)]
+    hands: Option<PathBuf>,
+
+    /// Definitions file of custom jokers, which rounds and runs can then use by name
+    #[arg(long, value_name = "FILE", global = true)]
+    jokers: Option<PathBuf>,
+
+    /// Scores the round N times and reports statistics of the final scores.
+    /// Uses --explain-format json or yaml for machine readable output
+    #[arg(long, value_name = "N")]
+    simulate: Option<usize>,
+
+    /// Reports the exact probability of every final score instead of sampling.
+    /// Consumables are still used once with --seed, so the scores are for the cards they made.
+    /// Uses --explain-format json or yaml for machine readable output
+    #[arg(long, conflicts_with = "simulate")]
+    exact: bool,
+
+    /// Largest number of random branches --exact will score, every roll multiplies them
+    #[arg(long, default_value_t = 100_000, requires = "exact")]
+    max_branches: usize,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Finds the best cards to play from `cards_played` and `cards_held_in_hand` together.
+    /// Uses --explain-format json or yaml for machine readable output
+    Optimize {
+        file: PathBuf,
+
+        /// Number of plays to list
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+    },
+
+    /// Finds the joker order which scores the round highest.
+    /// Uses --explain-format json or yaml for machine readable output
+    OrderJokers {
+        file: PathBuf,
+
+        /// Orders to score before falling back to a local search
+        #[arg(long, default_value_t = DEFAULT_MAX_ORDERS)]
+        max_orders: usize,
+    },
+
+    /// Recommends which cards to discard, drawing replacements from `deck`.
+    /// Uses --explain-format json or yaml for machine readable output
+    Discard {
+        file: PathBuf,
+
+        /// Number of discards to list
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+
+        /// Draws to score for each discard, every draw is scored when there are fewer
+        #[arg(long, default_value_t = 50)]
+        draws: usize,
+    },
+
+    /// Tracks the cards left in the deck and the chance of drawing into each poker hand.
+    /// Uses --explain-format json or yaml for machine readable output
+    Deck {
+        file: PathBuf,
+
+        /// Number of cards to draw
+        #[arg(long, default_value_t = 1)]
+        draws: usize,
+
+        /// Draws to score, every draw is scored when there are fewer
+        #[arg(long, default_value_t = 2_000)]
+        samples: usize,
+    },
+
+    /// Scores hands one after another, carrying what scaling jokers such as Green Joker
+    /// build up from each hand to the next. Uses --explain-format json or yaml for machine
+    /// readable output
+    Sequence {
+        file: PathBuf,
+    },
+
+    /// Plays a whole run, reading commands from stdin: `play 0 1 2` and `discard 3` during a
+    /// blind, `buy 0`, `sell 1`, `reroll` and `next` in the shop, `pick 0` or `skip` with a
+    /// booster pack open. `use 0 2 3` uses the first consumable on cards 2 and 3 of the hand.
+    /// Uses --explain-format json for machine readable output
+    Run {
+        /// Run settings such as the jokers and stake, defaults to a plain run
+        file: Option<PathBuf>,
+    },
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExplainFormat {
+    Text,
+    Json,
+    Yaml,
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
     let opts = Opts::parse();
-    let (round, explain) = parse_round(&opts)?;
+    // Hands first so custom jokers can refer to them
+    if let Some(hands) = &opts.hands {
+        register_custom_hands(&read_input(hands)?)?;
+    }
+    if let Some(jokers) = &opts.jokers {
+        register_custom_jokers(&read_input(jokers)?)?;
+    }
+    if let Some(Command::Run { file }) = &opts.command {
+        return play_run(file.as_deref(), &opts);
+    }
+    if let Some(Command::Sequence { file }) = &opts.command {
+        return play_sequence(file, &opts);
+    }
+
+    let file = match &opts.command {
+        Some(
+            | Command::Optimize { file, .. }
+            | Command::OrderJokers { file, .. }
+            | Command::Discard { file, .. }
+            | Command::Deck { file, .. },
+        ) => file,
+        Some(Command::Run { .. } | Command::Sequence { .. }) => {
+            unreachable!("runs and sequences are handled above")
+        }
+        None => opts.file.as_ref().expect("file is required without a subcommand"),
+    };
+    let (round, explain) = parse_round(file, &opts)?;
+
+    let options = ScoreOptions {
+        explain: explain.is_some(),
+        target: opts.target,
+        seed: opts.seed,
+    };
+
+    if let Some(Command::Optimize { top, .. }) = opts.command {
+        let report = optimize(&round, &options, top);
+        match explain {
+            Some(ExplainFormat::Json) => println!("{}", serde_json::to_string_pretty(&report)?),
+            Some(ExplainFormat::Yaml) => print!("{}", serde_yaml::to_string(&report)?),
+            _ => print!("{}", report),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::OrderJokers { max_orders, .. }) = opts.command {
+        let report = optimize_joker_order(&round, &options, max_orders);
+        match explain {
+            Some(ExplainFormat::Json) => println!("{}", serde_json::to_string_pretty(&report)?),
+            Some(ExplainFormat::Yaml) => print!("{}", serde_yaml::to_string(&report)?),
+            _ => print!("{}", report),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(runs) = opts.simulate {
+        let report = simulate(&round, &options, runs);
+        match explain {
+            Some(ExplainFormat::Json) => println!("{}", serde_json::to_string_pretty(&report)?),
+            Some(ExplainFormat::Yaml) => print!("{}", serde_yaml::to_string(&report)?),
+            _ => print!("{}", report),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if opts.exact {
+        let distribution = exact_distribution(&round, &options, opts.max_branches)?;
+        match explain {
+            Some(ExplainFormat::Json) => {
+                println!("{}", serde_json::to_string_pretty(&distribution)?);
+            }
+            Some(ExplainFormat::Yaml) => print!("{}", serde_yaml::to_string(&distribution)?),
+            _ => print!("{}", distribution),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::Discard { top, draws, .. }) = opts.command {
+        let advice = advise_discard(&round, &options, top, draws)?;
+        match explain {
+            Some(ExplainFormat::Json) => println!("{}", serde_json::to_string_pretty(&advice)?),
+            Some(ExplainFormat::Yaml) => print!("{}", serde_yaml::to_string(&advice)?),
+            _ => print!("{}", advice),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::Deck { draws, samples, .. }) = opts.command {
+        let report = Deck::from_round(&round).report(&round, draws, samples, opts.seed);
+        match explain {
+            Some(ExplainFormat::Json) => println!("{}", serde_json::to_string_pretty(&report)?),
+            Some(ExplainFormat::Yaml) => print!("{}", serde_yaml::to_string(&report)?),
+            _ => print!("{}", report),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let report = score_input(&round, &options);
+
+    match explain {
+        Some(ExplainFormat::Json) => {
+            println!("{}", serde_json::to_string_pretty(&ExplainDocument::new(&round, &report))?);
+        }
+        Some(ExplainFormat::Yaml) => {
+            print!("{}", serde_yaml::to_string(&ExplainDocument::new(&round, &report))?);
+        }
+        Some(ExplainFormat::Text) => {
+            print_text(&report);
+        }
+        None => {
+            if let Some(violation) = report.violation {
+                eprintln!("Not allowed, {}", violation);
+            }
+            println!("{}", report.score);
+            print_outcome(&report);
+        }
+    }
+
+    // Scripts can branch on whether the blind was beaten
+    match report.outcome {
+        Some(outcome) if !outcome.passed => Ok(ExitCode::from(2)),
+        _ => Ok(ExitCode::SUCCESS),
+    }
+}
+
+fn print_text(report: &ScoreReport) {
+    report.explanation().iter().for_each(|line| println!("{}", line));
+    println!("{}", report.score);
+    print_outcome(report);
+}
+
+fn print_outcome(report: &ScoreReport) {
+    if let Some(outcome) = report.outcome {
+        println!("{}", outcome);
+    }
+    if !report.effects.is_empty() {
+        println!("{}", report.effects);
+    }
+}
+
+/// Scores every hand of a sequence file and prints each score along with the joker states
+fn play_sequence(file: &Path, opts: &Opts) -> Result<ExitCode, Box<dyn Error>> {
+    let sequence: HandSequence = serde_yaml::from_str(&read_input(file)?)?;
+    let options = ScoreOptions { explain: false, target: opts.target, seed: opts.seed };
+
+    let report = score_sequence(&sequence, &options);
+    match opts.explain_format {
+        Some(ExplainFormat::Json) => println!("{}", serde_json::to_string_pretty(&report)?),
+        Some(ExplainFormat::Yaml) => print!("{}", serde_yaml::to_string(&report)?),
+        _ => print!("{}", report),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Steps a run with commands read from stdin, one per line, printing what happened after each
+fn play_run(file: Option<&Path>, opts: &Opts) -> Result<ExitCode, Box<dyn Error>> {
+    let mut config: RunConfig = match file {
+        Some(file) => serde_yaml::from_str(&read_input(file)?)?,
+        None => RunConfig::default(),
+    };
+    config.seed = config.seed.or(Some(opts.seed));
+    let json = opts.explain_format == Some(ExplainFormat::Json);
+
+    let (mut run, events) = Run::new(config);
+    print_run(&run, &events, json)?;
+
+    for line in stdin().lines() {
+        let line = line?;
+        match line.trim() {
+            "" | "state" => print_run(&run, &[], json)?,
+            "quit" => {
+                break;
+            }
+            command => {
+                match command.parse::<RunCommand>().and_then(|command| run.apply(command)) {
+                    Ok(events) => print_run(&run, &events, json)?,
+                    Err(error) => eprintln!("{}", error),
+                }
+            }
+        }
+
+        if run.status() != RunStatus::Playing {
+            break;
+        }
+    }
 
-    let (chips, mult) = score(round, explain);
+    match run.status() {
+        RunStatus::Lost => Ok(ExitCode::from(2)),
+        _ => Ok(ExitCode::SUCCESS),
+    }
+}
+
+fn print_run(run: &Run, events: &[RunEvent], json: bool) -> Result<(), Box<dyn Error>> {
+    if json {
+        let output = serde_json::json!({ "events": events, "state": run.state() });
+        println!("{}", serde_json::to_string(&output)?);
+        return Ok(());
+    }
 
-    println!("{}", (chips * mult).floor());
+    events.iter().for_each(|event| println!("{}", event));
+    if run.status() == RunStatus::Playing {
+        println!("{}", run.state());
+    }
     Ok(())
 }
 
-fn parse_round(opts: &Opts) -> Result<(Round, bool), Box<dyn Error>> {
+fn read_input(file: &Path) -> Result<String, Box<dyn Error>> {
     let mut input = String::new();
-    if opts.file == Path::new("-") {
+    if file == Path::new("-") {
         stdin().read_to_string(&mut input)?;
     } else {
-        File::open(&opts.file)?.read_to_string(&mut input)?;
+        File::open(file)?.read_to_string(&mut input)?;
     }
-
-    let round = serde_yaml::from_str(&input)?;
-
-    // Return both the round and the explain flag
-    Ok((round, opts.explain))
+    Ok(input)
 }
 
-fn score(round: Round, explain: bool) -> (Chips, Mult) {
-    let mut scorer = RoundScorer::new(round);
-    scorer.get_score(explain)
+fn parse_round(
+    file: &Path,
+    opts: &Opts
+) -> Result<(RoundInput, Option<ExplainFormat>), Box<dyn Error>> {
+    let round = serde_yaml::from_str(&read_input(file)?)?;
+    check_consumable_targets(&round)?;
+
+    // Return both the round and the requested explain format
+    let explain = match opts.explain_format {
+        Some(format) => Some(format),
+        None if opts.explain => Some(ExplainFormat::Text),
+        None => None,
+    };
+    Ok((round, explain))
 }
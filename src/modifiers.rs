@@ -1,48 +1,70 @@
-use ortalib::{ Chips, Mult, Edition, Enhancement };
+use ortalib::{ Chips, Mult, Edition };
+
+use crate::cards::Enhancement;
+use crate::cardscorer::ScoringData;
+use crate::events::{ CardLabel, EditionOwner, EventKind, EventSource, ScoreEvent };
 
 pub fn get_played_enhancements(
     enhancement: Option<Enhancement>,
-    explain: &bool,
     curr_chips: &Chips,
     curr_mult: &Mult,
-    card_title: &String
+    card: CardLabel,
+    data: &ScoringData
 ) -> (Chips, Mult) {
     let mut bonus_chips: Chips = *curr_chips;
     let mut bonus_mult: Mult = *curr_mult;
-    match enhancement {
-        Some(Enhancement::Bonus) => {
+    let Some(enhancement) = enhancement else {
+        return (bonus_chips, bonus_mult);
+    };
+    let (kind, delta) = match enhancement {
+        Enhancement::Bonus => {
             bonus_chips += 30.0;
-            if *explain {
-                println!("{} Bonus +30 Chips( {:?} x {:?} )", card_title, bonus_chips, bonus_mult);
-            }
+            (EventKind::AddChips, 30.0)
         }
-        Some(Enhancement::Mult) => {
+        Enhancement::Mult => {
             bonus_mult += 4.0;
-            if *explain {
-                println!("{} Mult +4 Mult ( {:?} x {:?} )", card_title, bonus_chips, bonus_mult);
-            }
+            (EventKind::AddMult, 4.0)
         }
-        Some(Enhancement::Glass) => {
+        Enhancement::Glass => {
             bonus_mult *= 2.0;
-            if *explain {
-                println!("{} Glass x2 Mult ( {:?} x {:?} )", card_title, bonus_chips, bonus_mult);
+            (EventKind::XMult, 2.0)
+        }
+        Enhancement::Stone => {
+            bonus_chips += 50.0;
+            (EventKind::AddChips, 50.0)
+        }
+        Enhancement::Lucky => {
+            // Both rolls are independent, the money is only seen at end of round
+            let lucky_mult = data.chance(1, 5);
+            if data.chance_unscored(1, 15) {
+                data.earn(20);
+            }
+            if !lucky_mult {
+                return (bonus_chips, bonus_mult);
             }
+            bonus_mult += 20.0;
+            (EventKind::AddMult, 20.0)
         }
-        _ => {}
-    }
-    return (bonus_chips, bonus_mult);
+        _ => {
+            return (bonus_chips, bonus_mult);
+        }
+    };
+
+    let source = EventSource::Enhancement { enhancement, card };
+    data.record(ScoreEvent::new(source, kind, delta, bonus_chips, bonus_mult));
+    (bonus_chips, bonus_mult)
 }
 
 pub fn get_editions(
     edition: Option<Edition>,
-    card_title: &String,
-    explain: &bool,
+    owner: EditionOwner,
     curr_chips: &Chips,
-    curr_mult: &Mult
+    curr_mult: &Mult,
+    data: &ScoringData
 ) -> (Chips, Mult) {
     let (bonus_chips, bonus_mult) = match edition {
-        Some(Edition::Polychrome) => get_polychrome(card_title, explain, curr_chips, curr_mult),
-        Some(_) => get_foil_holo(edition, card_title, explain, curr_chips, curr_mult),
+        Some(Edition::Polychrome) => get_polychrome(owner, curr_chips, curr_mult, data),
+        Some(_) => get_foil_holo(edition, owner, curr_chips, curr_mult, data),
         _ => (*curr_chips, *curr_mult),
     };
     (bonus_chips, bonus_mult)
@@ -50,30 +72,27 @@ pub fn get_editions(
 
 pub fn get_foil_holo(
     edition: Option<Edition>,
-    card_title: &String,
-    explain: &bool,
+    owner: EditionOwner,
     curr_chips: &Chips,
-    curr_mult: &Mult
+    curr_mult: &Mult,
+    data: &ScoringData
 ) -> (Chips, Mult) {
     let mut bonus_chips = *curr_chips;
     let mut bonus_mult = *curr_mult;
     match edition {
         Some(Edition::Foil) => {
             bonus_chips += 50.0;
-            if *explain {
-                println!("{} Foil +50 Chips ( {:?} x {:?} )", card_title, bonus_chips, bonus_mult);
-            }
+            let source = EventSource::Edition { edition: Edition::Foil, owner };
+            data.record(
+                ScoreEvent::new(source, EventKind::AddChips, 50.0, bonus_chips, bonus_mult)
+            );
         }
         Some(Edition::Holographic) => {
             bonus_mult += 10.0;
-            if *explain {
-                println!(
-                    "{} Holographic +10 Mult ( {:?} x {:?} )",
-                    card_title,
-                    bonus_chips,
-                    bonus_mult
-                );
-            }
+            let source = EventSource::Edition { edition: Edition::Holographic, owner };
+            data.record(
+                ScoreEvent::new(source, EventKind::AddMult, 10.0, bonus_chips, bonus_mult)
+            );
         }
         _ => {}
     }
@@ -81,15 +100,14 @@ pub fn get_foil_holo(
 }
 
 pub fn get_polychrome(
-    card_title: &String,
-    explain: &bool,
+    owner: EditionOwner,
     curr_chips: &Chips,
-    curr_mult: &Mult
+    curr_mult: &Mult,
+    data: &ScoringData
 ) -> (Chips, Mult) {
     let bonus_chips = *curr_chips;
     let bonus_mult = *curr_mult * 1.5;
-    if *explain {
-        println!("{} Polychrome x1.5 Mult ( {:?} x {:?} )", card_title, bonus_chips, bonus_mult);
-    }
+    let source = EventSource::Edition { edition: Edition::Polychrome, owner };
+    data.record(ScoreEvent::new(source, EventKind::XMult, 1.5, bonus_chips, bonus_mult));
     (bonus_chips, bonus_mult)
 }
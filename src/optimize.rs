@@ -0,0 +1,336 @@
+use std::fmt::{ Display, Formatter };
+
+use serde::Serialize;
+
+use crate::blinds::BossBlind;
+use crate::cards::PlayingCard;
+use crate::consumables::with_consumables_used;
+use crate::input::RoundInput;
+use crate::roundscorer::{ ScoreOptions, ScoreReport };
+use crate::score_input;
+
+/// Most cards a single hand can play
+const MAX_PLAYED_CARDS: usize = 5;
+
+/// Joker orders scored before the search stops being exhaustive, every order of 8 jokers
+pub const DEFAULT_MAX_ORDERS: usize = 40_320;
+
+/// A choice of cards to play, scored with the rest of the hand held
+#[derive(Debug, Clone, Serialize)]
+pub struct Play {
+    pub cards: Vec<String>,
+    /// Positions of the played cards in the full hand, `cards_played` first
+    pub indices: Vec<usize>,
+    pub held: Vec<String>,
+    pub poker_hand: String,
+    pub score: f64,
+    /// Whether the play beats the blind target, if there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passed: Option<bool>,
+}
+
+/// Highest scoring plays from a full hand, best first
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizeReport {
+    pub hand_size: usize,
+    /// Number of legal plays which were scored
+    pub plays_considered: usize,
+    pub plays: Vec<Play>,
+    /// Cards and jokers changed by tarots and spectrals before the plays were chosen
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub card_changes: Vec<String>,
+}
+
+/// Jokers in a given order and the score the round gets with them
+#[derive(Debug, Clone, Serialize)]
+pub struct JokerOrder {
+    pub jokers: Vec<String>,
+    pub score: f64,
+}
+
+/// Best joker order found for a round, next to the order it was given in
+#[derive(Debug, Clone, Serialize)]
+pub struct JokerOrderReport {
+    pub current: JokerOrder,
+    pub best: JokerOrder,
+    pub orders_scored: usize,
+    /// False when there were too many orders and a local search was used instead
+    pub exhaustive: bool,
+}
+
+/// Scores every legal play of 1 to 5 cards from `cards_played` and `cards_held_in_hand`
+/// together, and returns the `top` highest scoring. Plays the boss blind forbids are skipped.
+/// Tarots and spectrals are used once on the hand as given, and the plays are chosen from the
+/// cards they leave.
+pub fn optimize(round: &RoundInput, options: &ScoreOptions, top: usize) -> OptimizeReport {
+    let options = ScoreOptions { explain: false, ..options.clone() };
+    let (round, changes) = with_consumables_used(round, options.seed);
+    let hand: Vec<PlayingCard> = round.cards_played
+        .iter()
+        .chain(round.cards_held_in_hand.iter())
+        .copied()
+        .collect();
+
+    // Scored plays as their positions, score and report, only the best become `Play`s
+    let mut scored: Vec<(Vec<usize>, ScoreReport)> = Vec::new();
+    let mut candidate = round.clone();
+    for size in 1..=MAX_PLAYED_CARDS.min(hand.len()) {
+        for indices in combinations(hand.len(), size) {
+            candidate.cards_played.clear();
+            candidate.cards_played.extend(indices.iter().map(|&index| hand[index]));
+            candidate.cards_held_in_hand.clear();
+            candidate.cards_held_in_hand.extend(
+                (0..hand.len()).filter(|index| !indices.contains(index)).map(|index| hand[index])
+            );
+
+            let report = score_input(&candidate, &options);
+            if report.violation.is_none() {
+                scored.push((indices, report));
+            }
+        }
+    }
+
+    // Stable, so among equal scores the play with fewer cards comes first
+    let plays_considered = scored.len();
+    scored.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+    let plays = scored
+        .into_iter()
+        .take(top)
+        .map(|(indices, report)| Play {
+            cards: indices
+                .iter()
+                .map(|&index| hand[index].to_string())
+                .collect(),
+            held: (0..hand.len())
+                .filter(|index| !indices.contains(index))
+                .map(|index| hand[index].to_string())
+                .collect(),
+            indices,
+            poker_hand: report.hand_name(),
+            score: report.score,
+            passed: report.outcome.map(|outcome| outcome.passed),
+        })
+        .collect();
+
+    OptimizeReport {
+        hand_size: hand.len(),
+        plays_considered,
+        plays,
+        card_changes: changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect(),
+    }
+}
+
+/// Searches for the joker order which scores the round highest. Every distinct order is tried
+/// when there are at most `max_orders` of them, otherwise the current order is improved by
+/// moving one joker at a time until no move helps or `max_orders` orders have been scored.
+/// The joker disabled by Crimson Heart stays disabled wherever it is moved.
+pub fn optimize_joker_order(
+    round: &RoundInput,
+    options: &ScoreOptions,
+    max_orders: usize
+) -> JokerOrderReport {
+    let options = ScoreOptions { explain: false, ..options.clone() };
+    let count = round.jokers.len();
+    let disabled = match round.boss_blind {
+        Some(BossBlind::CrimsonHeart) if count > 0 => {
+            Some(round.disabled_joker.unwrap_or(0).min(count - 1))
+        }
+        _ => None,
+    };
+
+    let score_order = |order: &[usize]| -> f64 {
+        let mut candidate = round.clone();
+        candidate.jokers = order.iter().map(|&index| round.jokers[index]).collect();
+        if let Some(disabled) = disabled {
+            candidate.disabled_joker = order.iter().position(|&index| index == disabled);
+        }
+        score_input(&candidate, &options).score
+    };
+
+    let current: Vec<usize> = (0..count).collect();
+    let current_score = score_order(&current);
+    let mut best = (current.clone(), current_score);
+    let mut orders_scored: usize = 1;
+
+    // Identical jokers are interchangeable, unless one of them is disabled
+    let classes: Vec<usize> = (0..count)
+        .map(|index| {
+            (0..index)
+                .find(|&other| {
+                    round.jokers[other] == round.jokers[index] &&
+                        disabled != Some(other) &&
+                        disabled != Some(index)
+                })
+                .unwrap_or(index)
+        })
+        .collect();
+    let exhaustive = distinct_orders(&classes) <= (max_orders as f64);
+
+    if exhaustive {
+        let mut order = classes.clone();
+        order.sort();
+        loop {
+            let indices = assign_classes(&order, &classes);
+            if indices != current {
+                let score = score_order(&indices);
+                orders_scored += 1;
+                if score > best.1 {
+                    best = (indices, score);
+                }
+            }
+            if !next_permutation(&mut order) {
+                break;
+            }
+        }
+    } else {
+        'search: loop {
+            let mut improved: Option<(Vec<usize>, f64)> = None;
+            for from in 0..count {
+                for to in (0..count).filter(|&to| to != from) {
+                    if orders_scored >= max_orders {
+                        break 'search;
+                    }
+
+                    let mut order = best.0.clone();
+                    let joker = order.remove(from);
+                    order.insert(to, joker);
+                    let score = score_order(&order);
+                    orders_scored += 1;
+
+                    let best_score = improved.as_ref().map_or(best.1, |(_, score)| *score);
+                    if score > best_score {
+                        improved = Some((order, score));
+                    }
+                }
+            }
+
+            match improved {
+                Some(order) => {
+                    best = order;
+                }
+                None => {
+                    break;
+                }
+            }
+        }
+    }
+
+    let describe = |order: &[usize], score: f64| JokerOrder {
+        jokers: order
+            .iter()
+            .map(|&index| round.jokers[index].to_string())
+            .collect(),
+        score,
+    };
+    JokerOrderReport {
+        current: describe(&current, current_score),
+        best: describe(&best.0, best.1),
+        orders_scored,
+        exhaustive,
+    }
+}
+
+/// Number of distinct orders of a list where equal classes are interchangeable
+fn distinct_orders(classes: &[usize]) -> f64 {
+    let factorial = |n: usize| (1..=n).map(|i| i as f64).product::<f64>();
+    let repeats: f64 = (0..classes.len())
+        .map(|class| factorial(classes.iter().filter(|&&other| other == class).count()))
+        .product();
+    factorial(classes.len()) / repeats
+}
+
+/// Turns an order of classes back into joker indices, taking equal jokers in their given order
+fn assign_classes(order: &[usize], classes: &[usize]) -> Vec<usize> {
+    let mut used = vec![false; classes.len()];
+    order
+        .iter()
+        .map(|&class| {
+            let index = (0..classes.len())
+                .find(|&index| classes[index] == class && !used[index])
+                .expect("order is a permutation of the classes");
+            used[index] = true;
+            index
+        })
+        .collect()
+}
+
+/// Rearranges into the next lexicographic permutation, returning false after the last one
+fn next_permutation(order: &mut [usize]) -> bool {
+    let Some(pivot) = (1..order.len()).rev().find(|&i| order[i - 1] < order[i]) else {
+        return false;
+    };
+    let successor = (pivot..order.len())
+        .rev()
+        .find(|&i| order[i] > order[pivot - 1])
+        .expect("pivot has a larger element after it");
+    order.swap(pivot - 1, successor);
+    order[pivot..].reverse();
+    true
+}
+
+/// Every increasing list of `size` indices below `n`, in lexicographic order
+pub(crate) fn combinations(n: usize, size: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut current: Vec<usize> = (0..size).collect();
+    if size > n {
+        return result;
+    }
+
+    loop {
+        result.push(current.clone());
+
+        // Advance the rightmost index which still has room to move
+        let Some(position) = (0..size).rev().find(|&i| current[i] < n - size + i) else {
+            return result;
+        };
+        current[position] += 1;
+        for i in position + 1..size {
+            current[i] = current[i - 1] + 1;
+        }
+    }
+}
+
+impl Display for JokerOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>10} {}", self.score, self.jokers.join(", "))
+    }
+}
+
+impl Display for JokerOrderReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Current: {}", self.current)?;
+        writeln!(f, "Best:    {}", self.best)?;
+        if self.exhaustive {
+            writeln!(f, "Scored all {} distinct orders", self.orders_scored)
+        } else {
+            writeln!(f, "Scored {} orders with a local search", self.orders_scored)
+        }
+    }
+}
+
+impl Display for OptimizeReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.card_changes {
+            writeln!(f, "{change}")?;
+        }
+        writeln!(f, "Best of {} plays from {} cards", self.plays_considered, self.hand_size)?;
+        for (rank, play) in self.plays.iter().enumerate() {
+            write!(
+                f,
+                "{:>3}. {:>10} {}: {}",
+                rank + 1,
+                play.score,
+                play.poker_hand,
+                play.cards.join(", ")
+            )?;
+            if let Some(passed) = play.passed {
+                write!(f, " ({})", if passed { "beats target" } else { "short of target" })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
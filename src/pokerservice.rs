@@ -1,13 +1,16 @@
-use ortalib::{ Card, Enhancement, Joker, PokerHand, Rank, Suit, SuitColor };
+use ortalib::{ PokerHand, Rank, Suit, SuitColor };
 use std::collections::HashMap;
+use crate::cards::{ Enhancement, PlayingCard };
 use crate::cardscorer::{ ScoringData, ScoringPlayedCard };
+use crate::jokers::EvaluationRules;
 use crate::utils::rank_value;
 
 /// Evaluates a given poker hand and returns the possible poker hands along with the
-/// cards that contribute to the winning hand.
+/// cards that contribute to the winning hand. Stone cards have no rank or suit, so they
+/// are left out of hand detection but always score.
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `data` - A reference to `ScoringData` containing round information, such as jokers used.
 ///
 /// # Returns
@@ -15,7 +18,66 @@ use crate::utils::rank_value;
 /// - A vector of `PokerHand` values representing the types of hands found in the played hand.
 /// - A vector of `ScoringPlayedCard` that contains the cards contributing to the best poker hands.
 pub fn evaluate_hand(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
+    data: &ScoringData
+) -> (Vec<PokerHand>, Vec<ScoringPlayedCard>) {
+    let ranked_hand: Vec<PlayingCard> = played_hand
+        .iter()
+        .filter(|card| !card.is_stone())
+        .copied()
+        .collect();
+
+    let (pokerhands, winning_cards) = if ranked_hand.is_empty() {
+        (vec![PokerHand::HighCard], Vec::new())
+    } else {
+        evaluate_ranked_hand(&ranked_hand, data)
+    };
+    if data.rules.every_card_scores {
+        return (pokerhands, collect_every_card(played_hand));
+    }
+    (pokerhands, collect_stone_cards(played_hand, winning_cards))
+}
+
+/// Every played card in order, for Splash
+fn collect_every_card(played_hand: &[PlayingCard]) -> Vec<ScoringPlayedCard> {
+    let positions: Vec<usize> = (0..played_hand.len()).collect();
+    collect_cards_at(played_hand, &positions)
+}
+
+/// The played cards at the given positions in order, for custom hands
+pub fn collect_cards_at(
+    played_hand: &[PlayingCard],
+    positions: &[usize]
+) -> Vec<ScoringPlayedCard> {
+    positions
+        .iter()
+        .filter_map(|&position| played_hand.get(position))
+        .enumerate()
+        .map(|(index, card)| ScoringPlayedCard {
+            rank: card.rank,
+            suit: card.suit,
+            enhancement: card.enhancement,
+            edition: card.edition,
+            scored_card_index: index,
+            is_face: card.is_face(),
+            debuffed: false,
+            seal: card.seal,
+        })
+        .collect()
+}
+
+/// Evaluates a hand of cards which all have a rank and suit.
+///
+/// # Arguments
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
+/// * `data` - A reference to `ScoringData` containing round information, such as jokers used.
+///
+/// # Returns
+/// A tuple containing:
+/// - A vector of `PokerHand` values representing the types of hands found in the played hand.
+/// - A vector of `ScoringPlayedCard` that contains the cards contributing to the best poker hands.
+fn evaluate_ranked_hand(
+    played_hand: &[PlayingCard],
     data: &ScoringData
 ) -> (Vec<PokerHand>, Vec<ScoringPlayedCard>) {
     let mut rank_count: HashMap<&Rank, u8> = HashMap::new();
@@ -24,24 +86,12 @@ pub fn evaluate_hand(
     let mut suits: Vec<Suit> = Vec::new();
     let mut pokerhands: Vec<PokerHand> = Vec::new();
     let mut winning_cards: Vec<ScoringPlayedCard> = Vec::new();
-    let mut is_fourfingers: bool = false;
-    let mut is_shortcut: bool = false;
-    let mut is_smeared: bool = false;
-
-    data.round.jokers.iter().for_each(|joker_card| {
-        match joker_card.joker {
-            Joker::FourFingers => {
-                is_fourfingers = true;
-            }
-            Joker::Shortcut => {
-                is_shortcut = true;
-            }
-            Joker::SmearedJoker => {
-                is_smeared = true;
-            }
-            _ => {}
-        }
-    });
+    let EvaluationRules {
+        four_fingers: is_fourfingers,
+        shortcut: is_shortcut,
+        smeared: is_smeared,
+        ..
+    } = data.rules;
 
     // Count occurrences of each rank and suit
     for card in played_hand {
@@ -99,7 +149,7 @@ pub fn evaluate_hand(
 
     let mut is_four_fingers_straight: bool = false;
     let is_straight = is_straight(
-        &played_hand,
+        played_hand,
         &is_fourfingers,
         &mut is_four_fingers_straight,
         &mut four_finger_straight_indices
@@ -110,7 +160,7 @@ pub fn evaluate_hand(
 
     if is_shortcut {
         shortcut_straight = is_shortcut_straight(
-            &played_hand,
+            played_hand,
             &is_shortcut,
             &mut four_shortcut_straight,
             &mut four_finger_shortcut_straight_indices
@@ -196,62 +246,52 @@ pub fn evaluate_hand(
     }
     pokerhands.push(PokerHand::HighCard);
 
-    if is_flush && is_five_of_a_kind {
-        collect_all_five(&played_hand, &mut winning_cards);
-    } else if is_flush && is_full_house {
-        collect_all_five(&played_hand, &mut winning_cards);
-    } else if is_five_of_a_kind {
-        collect_all_five(&played_hand, &mut winning_cards);
-    } else if is_flush && is_straight {
-        collect_all_five(&played_hand, &mut winning_cards);
-    } else if is_flush && shortcut_straight {
-        // 5 of same suit, 5 straight but shortcutted
-        collect_all_five(&played_hand, &mut winning_cards);
-    } else if is_four_fingers_flush && is_straight {
-        collect_all_five(&played_hand, &mut winning_cards);
+    if
+        // Five of a kind and flush five
+        is_five_of_a_kind ||
+        (is_flush && is_full_house) ||
+        // 5 of same suit, 5 straight which may be shortcutted
+        (is_flush && (is_straight || shortcut_straight)) ||
+        (is_four_fingers_flush && is_straight)
+    {
+        collect_all_five(played_hand, &mut winning_cards);
     } else if is_four_fingers_flush && is_four_fingers_straight {
         // 4 of same suit, straight but 4 and shortcutted
-        collect_four_flush(&played_hand, &mut winning_cards, &is_smeared);
+        collect_four_flush(played_hand, &mut winning_cards, &is_smeared);
     } else if is_four_of_a_kind {
-        collect_four_of_a_kind(&played_hand, &rank_count, &mut winning_cards);
-    } else if is_full_house {
-        collect_all_five(&played_hand, &mut winning_cards);
-    } else if is_flush {
-        collect_all_five(&played_hand, &mut winning_cards);
+        collect_four_of_a_kind(played_hand, &rank_count, &mut winning_cards);
+    } else if is_full_house || is_flush {
+        collect_all_five(played_hand, &mut winning_cards);
     } else if is_four_fingers_flush {
         // Four fingers flush
-        collect_four_flush(&played_hand, &mut winning_cards, &is_smeared);
-    } else if is_straight {
-        collect_all_five(&played_hand, &mut winning_cards);
-    } else if shortcut_straight {
-        // 5 Shortcut straight
-        collect_all_five(&played_hand, &mut winning_cards);
+        collect_four_flush(played_hand, &mut winning_cards, &is_smeared);
+    } else if is_straight || shortcut_straight {
+        // 5 straight or 5 Shortcut straight
+        collect_all_five(played_hand, &mut winning_cards);
     } else if is_four_fingers_straight {
         // 4 straight
-        collect_indices(&played_hand, &mut winning_cards, &four_finger_straight_indices);
+        collect_indices(played_hand, &mut winning_cards, &four_finger_straight_indices);
     } else if four_shortcut_straight {
         // 4 straight with shortcut
-        collect_indices(&played_hand, &mut winning_cards, &four_finger_shortcut_straight_indices);
+        collect_indices(played_hand, &mut winning_cards, &four_finger_shortcut_straight_indices);
     } else if is_three_of_a_kind {
-        collect_three_of_a_kind(&played_hand, &rank_count, &mut winning_cards);
-    } else if is_two_pair {
-        collect_pairs(&played_hand, &rank_count, &mut winning_cards);
-    } else if is_pair {
-        collect_pairs(&played_hand, &rank_count, &mut winning_cards);
+        collect_three_of_a_kind(played_hand, &rank_count, &mut winning_cards);
+    } else if is_two_pair || is_pair {
+        collect_pairs(played_hand, &rank_count, &mut winning_cards);
     } else {
-        collect_high_card(&played_hand, &mut winning_cards);
+        collect_high_card(played_hand, &mut winning_cards);
     }
 
     pokerhands.sort();
     pokerhands.reverse();
 
-    return (pokerhands, winning_cards);
+    (pokerhands, winning_cards)
 }
 
 /// Checks whether a hand contains a valid straight (5 consecutive ranks).
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `is_four_fingers` - A boolean indicating whether "Four Fingers" is active.
 /// * `is_four_fingers_straight` - A mutable reference to a boolean indicating if it's a four-card straight.
 /// * `indexes` - A mutable reference to a vector of indices that tracks the winning cards.
@@ -259,7 +299,7 @@ pub fn evaluate_hand(
 /// # Returns
 /// `true` if the hand contains a valid straight, `false` otherwise.
 fn is_straight(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
     is_four_fingers: &bool,
     is_four_fingers_straight: &mut bool,
     indexes: &mut Vec<usize>
@@ -296,7 +336,7 @@ fn is_straight(
     // Standard 5‑card straight check.
     if played_hand.len() == 5 {
         let mut rank_values: Vec<u8> = played_hand
-            .into_iter()
+            .iter()
             .map(|&card| rank_value(card.rank))
             .collect();
         rank_values.sort();
@@ -346,7 +386,7 @@ fn is_straight(
 /// Checks whether a hand contains a valid shortcut straight (cards with ranks that differ by 1 or 2).
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `is_four_fingers` - A boolean indicating whether "Four Fingers" is active.
 /// * `four_shortcut_straight` - A mutable reference to a boolean indicating if it's a four-card shortcut straight.
 /// * `indexes` - A mutable reference to a vector of indices that tracks the winning cards.
@@ -354,7 +394,7 @@ fn is_straight(
 /// # Returns
 /// `true` if the hand contains a valid shortcut straight, `false` otherwise.
 fn is_shortcut_straight(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
     is_four_fingers: &bool,
     four_shortcut_straight: &mut bool,
     indexes: &mut Vec<usize>
@@ -431,17 +471,19 @@ fn is_shortcut_straight(
 /// Collects all five cards from the hand and adds them to the winning cards vector.
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `winning_cards` - A mutable reference to a vector of `ScoringPlayedCard` where the winning cards are stored.
-fn collect_all_five(played_hand: &Vec<Card>, winning_cards: &mut Vec<ScoringPlayedCard>) {
-    played_hand.into_iter().for_each(|card| {
+fn collect_all_five(played_hand: &[PlayingCard], winning_cards: &mut Vec<ScoringPlayedCard>) {
+    played_hand.iter().for_each(|card| {
         winning_cards.push(ScoringPlayedCard {
             rank: card.rank,
             suit: card.suit,
             enhancement: card.enhancement,
             edition: card.edition,
             scored_card_index: winning_cards.len(),
-            is_face: card.rank.is_face(),
+            is_face: card.is_face(),
+            debuffed: false,
+            seal: card.seal,
         });
     });
 }
@@ -449,26 +491,29 @@ fn collect_all_five(played_hand: &Vec<Card>, winning_cards: &mut Vec<ScoringPlay
 /// Collects the four of a kind cards from the hand and adds them to the winning cards vector.
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `rank_count` - A reference to a `HashMap` of `Rank` and their occurrences in the played hand.
 /// * `winning_cards` - A mutable reference to a vector of `ScoringPlayedCard` where the winning cards are stored.
 fn collect_four_of_a_kind(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
     rank_count: &HashMap<&Rank, u8>,
     winning_cards: &mut Vec<ScoringPlayedCard>
 ) {
-    played_hand.into_iter().for_each(|card| {
-        if let Some(&count) = rank_count.get(&card.rank) {
-            if count == 4 {
-                winning_cards.push(ScoringPlayedCard {
-                    rank: card.rank,
-                    suit: card.suit,
-                    enhancement: card.enhancement,
-                    edition: card.edition,
-                    scored_card_index: winning_cards.len(),
-                    is_face: card.rank.is_face(),
-                });
-            }
+    played_hand.iter().for_each(|card| {
+        if
+            let Some(&count) = rank_count.get(&card.rank) &&
+            count == 4
+        {
+            winning_cards.push(ScoringPlayedCard {
+                rank: card.rank,
+                suit: card.suit,
+                enhancement: card.enhancement,
+                edition: card.edition,
+                scored_card_index: winning_cards.len(),
+                is_face: card.is_face(),
+                debuffed: false,
+                seal: card.seal,
+            });
         }
     });
 }
@@ -476,26 +521,29 @@ fn collect_four_of_a_kind(
 /// Collects the three of a kind cards from the hand and adds them to the winning cards vector.
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `rank_count` - A reference to a `HashMap` of `Rank` and their occurrences in the played hand.
 /// * `winning_cards` - A mutable reference to a vector of `ScoringPlayedCard` where the winning cards are stored.
 fn collect_three_of_a_kind(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
     rank_count: &HashMap<&Rank, u8>,
     winning_cards: &mut Vec<ScoringPlayedCard>
 ) {
-    played_hand.into_iter().for_each(|card| {
-        if let Some(&count) = rank_count.get(&card.rank) {
-            if count == 3 {
-                winning_cards.push(ScoringPlayedCard {
-                    rank: card.rank,
-                    suit: card.suit,
-                    enhancement: card.enhancement,
-                    edition: card.edition,
-                    scored_card_index: winning_cards.len(),
-                    is_face: card.rank.is_face(),
-                });
-            }
+    played_hand.iter().for_each(|card| {
+        if
+            let Some(&count) = rank_count.get(&card.rank) &&
+            count == 3
+        {
+            winning_cards.push(ScoringPlayedCard {
+                rank: card.rank,
+                suit: card.suit,
+                enhancement: card.enhancement,
+                edition: card.edition,
+                scored_card_index: winning_cards.len(),
+                is_face: card.is_face(),
+                debuffed: false,
+                seal: card.seal,
+            });
         }
     });
 }
@@ -503,26 +551,29 @@ fn collect_three_of_a_kind(
 /// Collects pairs from the hand and adds them to the winning cards vector.
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `rank_count` - A reference to a `HashMap` of `Rank` and their occurrences in the played hand.
 /// * `winning_cards` - A mutable reference to a vector of `ScoringPlayedCard` where the winning cards are stored.
 fn collect_pairs(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
     rank_count: &HashMap<&Rank, u8>,
     winning_cards: &mut Vec<ScoringPlayedCard>
 ) {
-    played_hand.into_iter().for_each(|card| {
-        if let Some(&count) = rank_count.get(&card.rank) {
-            if count == 2 {
-                winning_cards.push(ScoringPlayedCard {
-                    rank: card.rank,
-                    suit: card.suit,
-                    enhancement: card.enhancement,
-                    edition: card.edition,
-                    scored_card_index: winning_cards.len(),
-                    is_face: card.rank.is_face(),
-                });
-            }
+    played_hand.iter().for_each(|card| {
+        if
+            let Some(&count) = rank_count.get(&card.rank) &&
+            count == 2
+        {
+            winning_cards.push(ScoringPlayedCard {
+                rank: card.rank,
+                suit: card.suit,
+                enhancement: card.enhancement,
+                edition: card.edition,
+                scored_card_index: winning_cards.len(),
+                is_face: card.is_face(),
+                debuffed: false,
+                seal: card.seal,
+            });
         }
     });
 }
@@ -530,12 +581,12 @@ fn collect_pairs(
 /// Collects the highest card from the hand and adds it to the winning cards vector.
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `winning_cards` - A mutable reference to a vector of `ScoringPlayedCard` where the highest card is stored.
-fn collect_high_card(played_hand: &Vec<Card>, winning_cards: &mut Vec<ScoringPlayedCard>) {
+fn collect_high_card(played_hand: &[PlayingCard], winning_cards: &mut Vec<ScoringPlayedCard>) {
     let card = played_hand
         .iter()
-        .max_by_key(|card| rank_value(card.rank) as u8)
+        .max_by_key(|card| rank_value(card.rank))
         .unwrap();
     winning_cards.push(ScoringPlayedCard {
         rank: card.rank,
@@ -543,26 +594,73 @@ fn collect_high_card(played_hand: &Vec<Card>, winning_cards: &mut Vec<ScoringPla
         enhancement: card.enhancement,
         edition: card.edition,
         scored_card_index: winning_cards.len(),
-        is_face: card.rank.is_face(),
+        is_face: card.is_face(),
+        debuffed: false,
+        seal: card.seal,
     });
 }
 
+/// Adds stone cards to the winning cards, keeping the order the cards were played in.
+///
+/// # Arguments
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
+/// * `winning_cards` - The winning cards found without stone cards, in played order.
+///
+/// # Returns
+/// The winning cards with stone cards included and their scored indices updated.
+fn collect_stone_cards(
+    played_hand: &[PlayingCard],
+    winning_cards: Vec<ScoringPlayedCard>
+) -> Vec<ScoringPlayedCard> {
+    let mut remaining = winning_cards.into_iter().peekable();
+    let mut merged: Vec<ScoringPlayedCard> = Vec::new();
+
+    played_hand.iter().for_each(|card| {
+        if card.is_stone() {
+            merged.push(ScoringPlayedCard {
+                rank: card.rank,
+                suit: card.suit,
+                enhancement: card.enhancement,
+                edition: card.edition,
+                scored_card_index: 0,
+                is_face: false,
+                debuffed: false,
+                seal: card.seal,
+            });
+        } else if
+            let Some(next) = remaining.peek() &&
+            next.rank == card.rank &&
+            next.suit == card.suit &&
+            next.enhancement == card.enhancement &&
+            next.edition == card.edition
+        {
+            merged.extend(remaining.next());
+        }
+    });
+    merged.extend(remaining);
+
+    merged.iter_mut().enumerate().for_each(|(index, card)| {
+        card.scored_card_index = index;
+    });
+    merged
+}
+
 /// Collects cards from the hand based on specific indices for Four Fingers + Shortcut
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `winning_cards` - A mutable reference to a vector of `ScoringPlayedCard` where the selected cards are stored.
 /// * `indices` - A vector of indices indicating the cards that should be added to the winning hand.
 fn collect_indices(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
     winning_cards: &mut Vec<ScoringPlayedCard>,
-    indices: &Vec<usize>
+    indices: &[usize]
 ) {
     played_hand
-        .into_iter()
+        .iter()
         .enumerate()
         .for_each(|(index, card)| {
-            indices.into_iter().for_each(|value| {
+            indices.iter().for_each(|value| {
                 if index == *value {
                     winning_cards.push(ScoringPlayedCard {
                         rank: card.rank,
@@ -570,7 +668,9 @@ fn collect_indices(
                         enhancement: card.enhancement,
                         edition: card.edition,
                         scored_card_index: winning_cards.len(),
-                        is_face: card.rank.is_face(),
+                        is_face: card.is_face(),
+                        debuffed: false,
+                        seal: card.seal,
                     });
                 }
             });
@@ -580,11 +680,11 @@ fn collect_indices(
 /// Collects cards from a four figners flush hand and adds them to the winning cards vector.
 ///
 /// # Arguments
-/// * `played_hand` - A vector of `Card` objects representing the hand played.
+/// * `played_hand` - A vector of `PlayingCard` objects representing the hand played.
 /// * `winning_cards` - A mutable reference to a vector of `ScoringPlayedCard` where the selected flush cards are stored.
 /// * `is_smeared` - A boolean indicating whether the cards have a smeared effect, affecting which suits to count.
 fn collect_four_flush(
-    played_hand: &Vec<Card>,
+    played_hand: &[PlayingCard],
     winning_cards: &mut Vec<ScoringPlayedCard>,
     is_smeared: &bool
 ) {
@@ -622,7 +722,9 @@ fn collect_four_flush(
                         enhancement: card.enhancement,
                         edition: card.edition,
                         scored_card_index: winning_cards.len(),
-                        is_face: card.rank.is_face(),
+                        is_face: card.is_face(),
+                        debuffed: false,
+                        seal: card.seal,
                     });
                     collected += 1;
                     if collected == 4 {
@@ -637,7 +739,9 @@ fn collect_four_flush(
                         enhancement: card.enhancement,
                         edition: card.edition,
                         scored_card_index: winning_cards.len(),
-                        is_face: card.rank.is_face(),
+                        is_face: card.is_face(),
+                        debuffed: false,
+                        seal: card.seal,
                     });
                     collected += 1;
                     if collected == 4 {
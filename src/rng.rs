@@ -0,0 +1,43 @@
+/// Source of randomness for probabilistic effects such as Lucky cards.
+/// Implement this to replay or enumerate outcomes instead of sampling them.
+pub trait GameRng {
+    /// Returns a uniformly random value in `0..sides`
+    fn roll(&mut self, sides: u32) -> u32;
+
+    /// Returns true with probability `numerator / denominator`
+    fn chance(&mut self, numerator: u32, denominator: u32) -> bool {
+        self.roll(denominator) < numerator
+    }
+
+    /// Same as `chance` for a roll which can never change chips or mult, such as a Glass card
+    /// breaking. Enumerating outcomes may take either result without following both.
+    fn chance_unscored(&mut self, numerator: u32, denominator: u32) -> bool {
+        self.chance(numerator, denominator)
+    }
+}
+
+/// Deterministic SplitMix64 generator, the same seed always gives the same rolls
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> SeededRng {
+        SeededRng { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl GameRng for SeededRng {
+    fn roll(&mut self, sides: u32) -> u32 {
+        (self.next_u64() % u64::from(sides.max(1))) as u32
+    }
+}
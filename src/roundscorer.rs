@@ -1,40 +1,249 @@
-use ortalib::{ Chips, Mult, Round };
+use std::cell::RefCell;
+use std::collections::HashSet;
 
-use crate::cardscorer::ScoringData;
+use ortalib::{ Chips, Mult, PokerHand };
 
-pub struct RoundScorer {
-    round: Round,
+use crate::cardscorer::{ ScoringData, ScoringPlayedCard };
+use crate::effects::RoundEffects;
+use crate::events::ScoreEvent;
+use crate::blinds::{ BlindViolation, BossBlind };
+use crate::cards::PlayingCard;
+use crate::consumables::CardChange;
+use crate::pokerservice::evaluate_hand;
+use crate::input::RoundInput;
+use crate::jokers::{ EvaluationRules, joker_effect };
+use crate::jokerstates::{ JokerStates, distinct_jokers };
+use crate::optimize::combinations;
+use crate::rng::GameRng;
+use crate::target::TargetOutcome;
+
+/// Options controlling how a round is scored
+#[derive(Debug, Clone, Default)]
+pub struct ScoreOptions {
+    /// Records every scoring step as a `ScoreEvent`
+    pub explain: bool,
+    /// Score needed to beat the blind, overrides the target of the round
+    pub target: Option<f64>,
+    /// Seed for Lucky cards and other random effects
+    pub seed: u64,
+}
+
+/// Result of scoring a round
+#[derive(Debug, Clone)]
+pub struct ScoreReport {
+    pub chips: Chips,
+    pub mult: Mult,
+    pub score: f64,
+    pub poker_hand: PokerHand,
+    /// Registered custom hand which outranked `poker_hand` and was scored instead
+    pub custom_hand: Option<&'static str>,
+    /// Level of the poker hand after consumables were used, custom hands are always level 1
+    pub hand_level: u32,
+    /// Chips and mult of the poker hand at its level before any cards are scored
+    pub base_chips: Chips,
+    pub base_mult: Mult,
+    pub scored_cards: Vec<ScoringPlayedCard>,
+    /// Set when the boss blind does not allow the hand, the score is then zero
+    pub violation: Option<BlindViolation>,
+    /// Result against the blind target, if the round has one
+    pub outcome: Option<TargetOutcome>,
+    /// Money, consumables and destroyed cards from playing the hand
+    pub effects: RoundEffects,
+    /// Scoring steps in order, empty unless `ScoreOptions::explain` is set
+    pub events: Vec<ScoreEvent>,
+    /// Cards and jokers changed by tarots and spectrals before the hand was scored
+    pub card_changes: Vec<CardChange>,
+    /// What scaling jokers have built up once the hand has scored
+    pub joker_states: JokerStates,
+}
+
+impl ScoreReport {
+    /// Name of the hand which was scored, the custom hand if there was one
+    pub fn hand_name(&self) -> String {
+        match self.custom_hand {
+            Some(name) => name.to_string(),
+            None => self.poker_hand.to_string(),
+        }
+    }
+
+    /// Renders the report as human readable explanation lines
+    pub fn explanation(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.card_changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect();
+        lines.push(
+            format!(
+                "{} lvl.{} ( {:?} x {:?} )",
+                self.hand_name(),
+                self.hand_level,
+                self.base_chips,
+                self.base_mult
+            )
+        );
+        lines.extend(self.events.iter().map(|event| event.to_string()));
+        if let Some(violation) = self.violation {
+            lines.push(format!("Not allowed, {}", violation));
+        }
+        lines
+    }
+}
+
+pub struct RoundScorer<'a> {
+    round: &'a RoundInput,
+    rng: &'a mut dyn GameRng,
     total_chips: f64,
     total_mult: f64,
+    poker_hand: PokerHand,
+    custom_hand: Option<&'static str>,
+    hand_level: u32,
+    base_chips: Chips,
+    base_mult: Mult,
+    scored_cards: Vec<ScoringPlayedCard>,
+    violation: Option<BlindViolation>,
+    effects: RoundEffects,
+    events: Vec<ScoreEvent>,
+    joker_states: JokerStates,
 }
 
-impl RoundScorer {
-    pub fn new(round: Round) -> RoundScorer {
-        RoundScorer { round, total_chips: 0.0, total_mult: 0.0 }
+impl<'a> RoundScorer<'a> {
+    pub fn new(round: &'a RoundInput, rng: &'a mut dyn GameRng) -> RoundScorer<'a> {
+        RoundScorer {
+            round,
+            rng,
+            total_chips: 0.0,
+            total_mult: 0.0,
+            poker_hand: PokerHand::HighCard,
+            custom_hand: None,
+            hand_level: 1,
+            base_chips: 0.0,
+            base_mult: 0.0,
+            scored_cards: Vec::new(),
+            violation: None,
+            effects: RoundEffects::default(),
+            events: Vec::new(),
+            joker_states: round.joker_states.clone(),
+        }
     }
 
     pub fn get_score(&mut self, explain: bool) -> (Chips, Mult) {
         self.calculate(explain);
-        return (self.total_chips, self.total_mult);
+        (self.total_chips, self.total_mult)
     }
 
-    fn calculate(&mut self, explain: bool) {
-        if explain {
-            println!("{:?}", self.round);
+    /// Consumes the scorer, returning everything learnt while scoring
+    pub fn into_report(self) -> ScoreReport {
+        ScoreReport {
+            chips: self.total_chips,
+            mult: self.total_mult,
+            score: (self.total_chips * self.total_mult).floor(),
+            poker_hand: self.poker_hand,
+            custom_hand: self.custom_hand,
+            hand_level: self.hand_level,
+            base_chips: self.base_chips,
+            base_mult: self.base_mult,
+            scored_cards: self.scored_cards,
+            violation: self.violation,
+            outcome: None,
+            effects: self.effects,
+            events: self.events,
+            card_changes: Vec::new(),
+            joker_states: self.joker_states,
         }
+    }
+
+    /// Poker hands which a play of at most five of the given cards would contain, using the
+    /// round's jokers such as Four Fingers, Shortcut and Smeared Joker
+    pub fn contained_hands(&mut self, cards: &[PlayingCard]) -> HashSet<PokerHand> {
+        let data = self.scoring_data(false);
+        if cards.len() <= 5 {
+            return evaluate_hand(cards, &data).0.into_iter().collect();
+        }
+
+        combinations(cards.len(), 5)
+            .iter()
+            .flat_map(|indices| {
+                let play: Vec<PlayingCard> = indices.iter().map(|&index| cards[index]).collect();
+                evaluate_hand(&play, &data).0
+            })
+            .collect()
+    }
+
+    fn scoring_data(&mut self, explain: bool) -> ScoringData<'_> {
+        let round = self.round;
 
-        let round = &self.round;
-        let mut scoring_data = ScoringData {
+        // Planets level up hands before the hand is scored
+        let mut hand_levels = round.hand_levels.clone();
+        round.consumables.iter().for_each(|consumable| consumable.apply(&mut hand_levels));
+
+        // Crimson Heart disables a single joker
+        let disabled_joker = match round.boss_blind {
+            Some(BossBlind::CrimsonHeart) if !round.jokers.is_empty() => {
+                Some(round.disabled_joker.unwrap_or(0).min(round.jokers.len() - 1))
+            }
+            _ => None,
+        };
+
+        let joker_effects = distinct_jokers(&round.jokers)
+            .into_iter()
+            .map(|joker| (joker, joker_effect(joker)))
+            .collect();
+
+        let mut data = ScoringData {
             played_cards: round.cards_played.clone(),
             scored_cards: Vec::new(),
             round,
             pokerhands: Vec::new(),
+            custom_hands: Vec::new(),
+            custom_hand: None,
             hand_cards: Vec::new(),
             joker_cards: Vec::new(),
+            joker_effects,
+            rules: EvaluationRules::default(),
+            hand_levels,
+            joker_states: RefCell::new(round.joker_states.clone()),
+            base_chips: 0.0,
+            base_mult: 0.0,
+            disabled_joker,
+            violation: None,
+            explain,
+            events: RefCell::new(Vec::new()),
+            effects: RefCell::new(RoundEffects::default()),
+            rng: RefCell::new(&mut *self.rng),
+        };
+        data.rules = data.evaluation_rules();
+        data
+    }
+
+    fn calculate(&mut self, explain: bool) {
+        let mut scoring_data = self.scoring_data(explain);
+        let (played_chips, played_mult) = scoring_data.get_score();
+
+        let poker_hand = scoring_data.pokerhands[0];
+        let custom_hand = scoring_data.custom_hand.as_ref().map(|hand| hand.name);
+        let hand_level = match custom_hand {
+            Some(_) => 1,
+            None => scoring_data.hand_levels.level(poker_hand),
         };
+        let base_chips = scoring_data.base_chips;
+        let base_mult = scoring_data.base_mult;
+        let violation = scoring_data.violation;
+        let scored_cards = scoring_data.scored_cards;
+        let events = scoring_data.events.into_inner();
+        let effects = scoring_data.effects.into_inner();
+        let joker_states = scoring_data.joker_states.into_inner();
 
-        let (played_chips, played_mult) = scoring_data.get_score(&explain);
         self.total_chips += played_chips;
         self.total_mult += played_mult;
+        self.poker_hand = poker_hand;
+        self.custom_hand = custom_hand;
+        self.hand_level = hand_level;
+        self.base_chips = base_chips;
+        self.base_mult = base_mult;
+        self.violation = violation;
+        self.scored_cards = scored_cards;
+        self.events = events;
+        self.effects = effects;
+        self.joker_states = joker_states;
     }
 }
@@ -0,0 +1,980 @@
+use std::fmt::{ Display, Formatter };
+use std::str::FromStr;
+
+use ortalib::PokerHand;
+use serde::{ Deserialize, Serialize };
+
+use crate::blinds::{ BOSS_BLINDS, BossBlind };
+use crate::cards::{ JokerCard, PlayingCard, Seal };
+use crate::consumables::{ ChangedCard, Consumable, Tarot, apply_consumables };
+use crate::deck::Deck;
+use crate::effects::CreatedConsumable;
+use crate::handlevels::HandLevels;
+use crate::input::{ RoundContextInput, RoundHistory, RoundInput, RunHistory };
+use crate::jokerstates::JokerStates;
+use crate::rng::{ GameRng, SeededRng };
+use crate::roundscorer::ScoreOptions;
+use crate::score_with_rng;
+use crate::shop::{ Payout, Shop, ShopItem, sell_value };
+use crate::target::{ BlindType, Stake, blind_target };
+use crate::utils::rank_value;
+
+/// Most cards a single play or discard can use
+const MAX_SELECTED_CARDS: usize = 5;
+
+/// Tarots a Purple Seal can create
+const TAROTS: [Tarot; 15] = {
+    use crate::consumables::Tarot::*;
+
+    [
+        TheMagician, TheEmpress, TheHierophant, TheLovers, TheChariot, Justice, Strength,
+        TheHangedMan, Death, TheDevil, TheTower, TheStar, TheMoon, TheSun, TheWorld,
+    ]
+};
+
+/// Settings a run starts from, read from the run file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RunConfig {
+    /// Seed for the deck shuffles, boss blinds and random effects
+    pub seed: Option<u64>,
+    pub stake: Stake,
+    pub hand_size: usize,
+    /// Hands which can be played each blind
+    pub hands: u32,
+    /// Discards which can be used each blind
+    pub discards: u32,
+    /// Ante whose boss blind has to be beaten to win the run
+    pub final_ante: u32,
+    pub money: i64,
+    pub joker_slots: usize,
+    pub jokers: Vec<JokerCard>,
+    /// Cards in the deck, defaults to the standard 52 cards
+    pub deck: Option<Vec<PlayingCard>>,
+    pub hand_levels: HandLevels,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        RunConfig {
+            seed: None,
+            stake: Stake::default(),
+            hand_size: 8,
+            hands: 4,
+            discards: 3,
+            final_ante: 8,
+            money: 4,
+            joker_slots: 5,
+            jokers: Vec::new(),
+            deck: None,
+            hand_levels: HandLevels::default(),
+        }
+    }
+}
+
+/// An action taken by the player. Cards are positions in the current hand, offers are
+/// positions in the shop and jokers are positions in the joker list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunCommand {
+    Play(Vec<usize>),
+    Discard(Vec<usize>),
+    Buy(usize),
+    Sell(usize),
+    Reroll,
+    /// Leaves the shop for the next blind
+    Next,
+    /// Takes a card from the opened booster pack
+    Pick(usize),
+    /// Closes the opened booster pack without taking anything
+    Skip,
+    /// Uses a consumable on the cards at the given positions of the hand
+    Use {
+        consumable: usize,
+        targets: Vec<usize>,
+    },
+}
+
+/// Where the run is between commands
+#[derive(Debug, Clone)]
+enum RunPhase {
+    Blind,
+    Shop(Shop),
+    /// Picking from an opened booster pack, returning to the shop afterwards
+    Pack {
+        shop: Shop,
+        choices: Vec<ShopItem>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// Something which happened while applying a command
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RunEvent {
+    BlindStarted {
+        ante: u32,
+        blind: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        boss_blind: Option<String>,
+        target: f64,
+    },
+    Played {
+        cards: Vec<String>,
+        poker_hand: String,
+        score: f64,
+        /// Score of the blind so far
+        total: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        violation: Option<String>,
+    },
+    Discarded {
+        cards: Vec<String>,
+    },
+    Used {
+        consumable: String,
+        /// What happened to each changed card, such as `K♥ becomes K♥ Glass`
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        changes: Vec<String>,
+    },
+    Earned {
+        money: i64,
+    },
+    CreatedConsumable {
+        consumable: String,
+    },
+    CardDestroyed {
+        card: String,
+    },
+    BlindDefeated {
+        ante: u32,
+        blind: String,
+        score: f64,
+    },
+    Payout {
+        blind_reward: i64,
+        hands: i64,
+        interest: i64,
+    },
+    Bought {
+        item: String,
+        price: i64,
+    },
+    Sold {
+        joker: String,
+        money: i64,
+    },
+    Rerolled {
+        cost: i64,
+    },
+    PackOpened {
+        pack: String,
+        choices: Vec<String>,
+    },
+    Picked {
+        item: String,
+    },
+    Won {
+        ante: u32,
+    },
+    Lost {
+        ante: u32,
+        blind: String,
+        score: f64,
+        target: f64,
+    },
+}
+
+/// Snapshot of a run between commands
+#[derive(Debug, Clone, Serialize)]
+pub struct RunState {
+    pub status: RunStatus,
+    /// `blind`, `shop` or `pack`
+    pub phase: String,
+    pub ante: u32,
+    pub blind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boss_blind: Option<String>,
+    pub target: f64,
+    pub score: f64,
+    pub hands_left: u32,
+    pub discards_left: u32,
+    pub money: i64,
+    pub hand: Vec<String>,
+    pub draw_pile: usize,
+    pub consumables: Vec<String>,
+    pub jokers: Vec<String>,
+    pub joker_slots: usize,
+    /// Offers and their prices while in the shop
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reroll_cost: Option<i64>,
+    /// Cards to pick from while a booster pack is open
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pack: Vec<String>,
+}
+
+/// A seeded run through the antes. Each blind shuffles the whole deck, deals up to the hand
+/// size and is beaten by reaching its target within the hands available. Every played hand is
+/// scored with `RoundScorer`, so boss blinds and jokers work as they do for a single round.
+/// Beating a blind pays out money and opens the shop until `Next` starts the next blind.
+pub struct Run {
+    config: RunConfig,
+    rng: SeededRng,
+    phase: RunPhase,
+    jokers: Vec<JokerCard>,
+    deck: Vec<PlayingCard>,
+    draw_pile: Vec<PlayingCard>,
+    hand: Vec<PlayingCard>,
+    ante: u32,
+    blind: BlindType,
+    /// Boss blind waiting at the end of the current ante
+    boss_blind: BossBlind,
+    hands_left: u32,
+    discards_left: u32,
+    score: f64,
+    money: i64,
+    hand_levels: HandLevels,
+    /// Created consumables waiting to be used, tarots are picked when they are created
+    consumables: Vec<Consumable>,
+    hands_played: Vec<PokerHand>,
+    /// Cards discarded since the last hand was played
+    discarded: Vec<PlayingCard>,
+    played_this_ante: Vec<PlayingCard>,
+    /// Whether a joker was sold during the current blind, which lifts Verdant Leaf
+    joker_sold: bool,
+    /// What scaling jokers have built up over the run
+    joker_states: JokerStates,
+    run_history: RunHistory,
+    status: RunStatus,
+}
+
+impl Run {
+    /// Starts a run at the small blind of ante 1, returning the run and its opening events
+    pub fn new(config: RunConfig) -> (Run, Vec<RunEvent>) {
+        let mut rng = SeededRng::new(config.seed.unwrap_or_default());
+        let boss_blind = BOSS_BLINDS[rng.roll(BOSS_BLINDS.len() as u32) as usize];
+        let mut run = Run {
+            rng,
+            phase: RunPhase::Blind,
+            jokers: config.jokers.clone(),
+            deck: config.deck.clone().unwrap_or_else(Deck::standard),
+            draw_pile: Vec::new(),
+            hand: Vec::new(),
+            ante: 1,
+            blind: BlindType::Small,
+            boss_blind,
+            hands_left: config.hands,
+            discards_left: config.discards,
+            score: 0.0,
+            money: config.money,
+            hand_levels: config.hand_levels.clone(),
+            consumables: Vec::new(),
+            hands_played: Vec::new(),
+            discarded: Vec::new(),
+            played_this_ante: Vec::new(),
+            joker_sold: false,
+            joker_states: JokerStates::default(),
+            run_history: RunHistory::default(),
+            status: RunStatus::Playing,
+            config,
+        };
+        let events = run.start_blind();
+        (run, events)
+    }
+
+    pub fn status(&self) -> RunStatus {
+        self.status
+    }
+
+    pub fn hand(&self) -> &[PlayingCard] {
+        &self.hand
+    }
+
+    pub fn jokers(&self) -> &[JokerCard] {
+        &self.jokers
+    }
+
+    pub fn money(&self) -> i64 {
+        self.money
+    }
+
+    /// Score needed to beat the current blind
+    pub fn target(&self) -> f64 {
+        blind_target(self.ante, self.config.stake, self.blind)
+    }
+
+    /// Negative jokers take no slot
+    fn jokers_in_slots(&self) -> usize {
+        self.jokers.iter().filter(|joker| !joker.negative).count()
+    }
+
+    fn active_boss_blind(&self) -> Option<BossBlind> {
+        (self.blind == BlindType::Boss).then_some(self.boss_blind)
+    }
+
+    pub fn state(&self) -> RunState {
+        let (phase, shop, pack) = match &self.phase {
+            RunPhase::Blind => ("blind", None, Vec::new()),
+            RunPhase::Shop(shop) => ("shop", Some(shop), Vec::new()),
+            RunPhase::Pack { choices, .. } => ("pack", None, choices.clone()),
+        };
+
+        RunState {
+            status: self.status,
+            phase: phase.to_string(),
+            ante: self.ante,
+            blind: self.blind.to_string(),
+            boss_blind: self.active_boss_blind().map(|boss_blind| boss_blind.to_string()),
+            target: self.target(),
+            score: self.score,
+            hands_left: self.hands_left,
+            discards_left: self.discards_left,
+            money: self.money,
+            hand: self.hand
+                .iter()
+                .map(|card| card.to_string())
+                .collect(),
+            draw_pile: self.draw_pile.len(),
+            consumables: self.consumables
+                .iter()
+                .map(|consumable| consumable.to_string())
+                .collect(),
+            jokers: self.jokers
+                .iter()
+                .map(|joker| joker.to_string())
+                .collect(),
+            joker_slots: self.config.joker_slots,
+            shop: shop
+                .map(|shop| {
+                    shop.offers
+                        .iter()
+                        .map(|offer| offer.to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            reroll_cost: shop.map(|shop| shop.reroll_cost),
+            pack: pack
+                .iter()
+                .map(|item| item.to_string())
+                .collect(),
+        }
+    }
+
+    /// Applies a command, failing without changing the run when it is not allowed
+    pub fn apply(&mut self, command: RunCommand) -> Result<Vec<RunEvent>, String> {
+        if self.status != RunStatus::Playing {
+            return Err("The run is over".to_string());
+        }
+
+        match (&self.phase, command) {
+            (RunPhase::Blind, RunCommand::Play(positions)) => {
+                self.check_selection(&positions)?;
+                Ok(self.play(&positions))
+            }
+            (RunPhase::Blind, RunCommand::Discard(positions)) => {
+                if self.discards_left == 0 {
+                    return Err("No discards left this blind".to_string());
+                }
+                self.check_selection(&positions)?;
+                Ok(self.discard(&positions))
+            }
+            (RunPhase::Blind | RunPhase::Shop(_), RunCommand::Sell(position)) => {
+                self.sell(position)
+            }
+            (RunPhase::Blind | RunPhase::Shop(_), RunCommand::Use { consumable, targets }) => {
+                self.use_consumable(consumable, &targets)
+            }
+            (RunPhase::Shop(_), RunCommand::Buy(position)) => self.buy(position),
+            (RunPhase::Shop(_), RunCommand::Reroll) => self.reroll(),
+            (RunPhase::Shop(_), RunCommand::Next) => {
+                self.phase = RunPhase::Blind;
+                Ok(self.start_blind())
+            }
+            (RunPhase::Pack { .. }, RunCommand::Pick(position)) => self.pick(position),
+            (RunPhase::Pack { .. }, RunCommand::Skip) => {
+                self.close_pack();
+                Ok(Vec::new())
+            }
+            (RunPhase::Blind, _) => {
+                Err("Only play, discard, use and sell during a blind".to_string())
+            }
+            (RunPhase::Shop(_), _) => {
+                Err("Only buy, sell, use, reroll and next in the shop".to_string())
+            }
+            (RunPhase::Pack { .. }, _) => Err("Only pick or skip with a pack open".to_string()),
+        }
+    }
+
+    fn sell(&mut self, position: usize) -> Result<Vec<RunEvent>, String> {
+        if position >= self.jokers.len() {
+            return Err(format!("No joker at position {position}"));
+        }
+
+        let joker = self.jokers.remove(position);
+        let money = sell_value(&joker);
+        self.money += money;
+        self.joker_sold = true;
+        Ok(vec![RunEvent::Sold { joker: joker.to_string(), money }])
+    }
+
+    /// Uses a consumable on the hand. Cards it changes stay changed in the deck, planets are
+    /// the only consumables which can be used outside a blind.
+    fn use_consumable(
+        &mut self,
+        position: usize,
+        targets: &[usize]
+    ) -> Result<Vec<RunEvent>, String> {
+        let Some(consumable) = self.consumables.get(position) else {
+            return Err(format!("No consumable at position {position}"));
+        };
+        let count = targets.len();
+        let consumable = match consumable {
+            Consumable::Planet(_) if count == 0 => consumable.clone(),
+            Consumable::Tarot { tarot, .. } if (1..=tarot.max_targets()).contains(&count) => {
+                Consumable::Tarot { tarot: *tarot, targets: targets.to_vec() }
+            }
+            Consumable::Spectral { spectral, .. } if count == spectral.targets() => {
+                Consumable::Spectral { spectral: *spectral, targets: targets.to_vec() }
+            }
+            _ => {
+                return Err(format!("{consumable} cannot target {count} cards"));
+            }
+        };
+        if let Consumable::Planet(_) = consumable {
+            self.consumables.remove(position);
+            consumable.apply(&mut self.hand_levels);
+            let changes = Vec::new();
+            return Ok(vec![RunEvent::Used { consumable: consumable.to_string(), changes }]);
+        }
+
+        if !matches!(self.phase, RunPhase::Blind) {
+            return Err("Tarots and spectrals can only be used during a blind".to_string());
+        }
+        if let Some(target) = targets.iter().find(|&&target| target >= self.hand.len()) {
+            return Err(format!("No card at position {target}, the hand has {}", self.hand.len()));
+        }
+
+        self.consumables.remove(position);
+        let mut round = RoundInput::new(Vec::new(), self.hand.clone(), self.jokers.clone());
+        round.consumables = vec![consumable.clone()];
+        let mut events = Vec::new();
+        if let Some(used) = apply_consumables(&round, &mut self.rng) {
+            self.hand = used.round.cards_held_in_hand;
+            self.jokers = used.round.jokers;
+            for change in &used.changes {
+                self.change_deck(change.before, change.after);
+            }
+            events.push(RunEvent::Used {
+                consumable: consumable.to_string(),
+                changes: used.changes
+                    .iter()
+                    .map(|change| change.outcome())
+                    .collect(),
+            });
+            self.earn(used.money, &mut events);
+        }
+        self.lose_if_out_of_cards(&mut events);
+        Ok(events)
+    }
+
+    /// Keeps the deck in step with a card a consumable changed, created or destroyed in hand
+    fn change_deck(&mut self, before: Option<ChangedCard>, after: Option<ChangedCard>) {
+        let playing = |card: Option<ChangedCard>| match card {
+            Some(ChangedCard::Playing(card)) => Some(card),
+            _ => None,
+        };
+        match (playing(before), playing(after)) {
+            (Some(before), after) => {
+                let Some(position) = self.deck.iter().position(|card| *card == before) else {
+                    return;
+                };
+                match after {
+                    Some(after) => {
+                        self.deck[position] = after;
+                    }
+                    None => {
+                        self.deck.remove(position);
+                    }
+                }
+            }
+            (None, Some(after)) => self.deck.push(after),
+            (None, None) => {}
+        }
+    }
+
+    fn buy(&mut self, position: usize) -> Result<Vec<RunEvent>, String> {
+        let jokers_in_slots = self.jokers_in_slots();
+        let RunPhase::Shop(shop) = &mut self.phase else {
+            return Err("Not in the shop".to_string());
+        };
+        let Some(&offer) = shop.offers.get(position) else {
+            return Err(format!("No offer at position {position}"));
+        };
+        if offer.price > self.money {
+            return Err(format!("Not enough money, {} costs ${}", offer.item, offer.price));
+        }
+        if
+            matches!(offer.item, ShopItem::Joker(_)) &&
+            jokers_in_slots >= self.config.joker_slots
+        {
+            return Err(format!("No free joker slots, {jokers_in_slots} are in use"));
+        }
+
+        shop.offers.remove(position);
+        self.money -= offer.price;
+        let mut events = vec![RunEvent::Bought { item: offer.item.to_string(), price: offer.price }];
+
+        if let ShopItem::Pack(pack) = offer.item {
+            let choices = pack.open(&mut self.rng);
+            events.push(RunEvent::PackOpened {
+                pack: pack.to_string(),
+                choices: choices
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect(),
+            });
+            let shop = shop.clone();
+            self.phase = RunPhase::Pack { shop, choices };
+        } else {
+            self.take_item(offer.item);
+        }
+        Ok(events)
+    }
+
+    fn reroll(&mut self) -> Result<Vec<RunEvent>, String> {
+        let RunPhase::Shop(shop) = &mut self.phase else {
+            return Err("Not in the shop".to_string());
+        };
+        let cost = shop.reroll_cost;
+        if cost > self.money {
+            return Err(format!("Not enough money, rerolling costs ${cost}"));
+        }
+
+        self.money -= cost;
+        shop.reroll(&mut self.rng);
+        Ok(vec![RunEvent::Rerolled { cost }])
+    }
+
+    fn pick(&mut self, position: usize) -> Result<Vec<RunEvent>, String> {
+        let RunPhase::Pack { choices, .. } = &self.phase else {
+            return Err("No pack is open".to_string());
+        };
+        let Some(&item) = choices.get(position) else {
+            return Err(format!("No card at position {position} of the pack"));
+        };
+        if matches!(item, ShopItem::Joker(_)) && self.jokers_in_slots() >= self.config.joker_slots {
+            return Err(format!("No free joker slots, {} are in use", self.jokers_in_slots()));
+        }
+
+        self.take_item(item);
+        self.close_pack();
+        Ok(vec![RunEvent::Picked { item: item.to_string() }])
+    }
+
+    fn close_pack(&mut self) {
+        if let RunPhase::Pack { shop, .. } = &self.phase {
+            self.phase = RunPhase::Shop(shop.clone());
+        }
+    }
+
+    /// Adds a bought or picked card to the run, planets are used straight away
+    fn take_item(&mut self, item: ShopItem) {
+        match item {
+            ShopItem::Joker(joker) => self.jokers.push(joker),
+            ShopItem::PlayingCard(card) => self.deck.push(card),
+            ShopItem::Planet(planet) => Consumable::Planet(planet).apply(&mut self.hand_levels),
+            ShopItem::Pack(_) => {}
+        }
+    }
+
+    fn check_selection(&self, positions: &[usize]) -> Result<(), String> {
+        if positions.is_empty() || positions.len() > MAX_SELECTED_CARDS {
+            return Err(format!("Select between 1 and {MAX_SELECTED_CARDS} cards"));
+        }
+        if let Some(position) = positions.iter().find(|&&position| position >= self.hand.len()) {
+            return Err(format!("No card at position {position}, the hand has {}", self.hand.len()));
+        }
+        if (1..positions.len()).any(|i| positions[..i].contains(&positions[i])) {
+            return Err("A card was selected more than once".to_string());
+        }
+        Ok(())
+    }
+
+    /// Takes the cards at the given positions out of the hand, keeping their order
+    fn take_cards(&mut self, positions: &[usize]) -> Vec<PlayingCard> {
+        let taken: Vec<PlayingCard> = positions
+            .iter()
+            .map(|&position| self.hand[position])
+            .collect();
+        self.hand = (0..self.hand.len())
+            .filter(|position| !positions.contains(position))
+            .map(|position| self.hand[position])
+            .collect();
+        taken
+    }
+
+    fn play(&mut self, positions: &[usize]) -> Vec<RunEvent> {
+        let played = self.take_cards(positions);
+        let disabled_joker = match self.active_boss_blind() {
+            Some(BossBlind::CrimsonHeart) if !self.jokers.is_empty() => {
+                Some(self.rng.roll(self.jokers.len() as u32) as usize)
+            }
+            _ => None,
+        };
+
+        let round = RoundInput {
+            cards_played: played.clone(),
+            cards_held_in_hand: self.hand.clone(),
+            jokers: self.jokers.clone(),
+            hand_levels: self.hand_levels.clone(),
+            consumables: Vec::new(),
+            boss_blind: self.active_boss_blind(),
+            cards_previously_played: self.played_this_ante.clone(),
+            cards_discarded: self.discarded.clone(),
+            disabled_joker,
+            history: RoundHistory {
+                hands_played_this_round: self.hands_played.clone(),
+                joker_sold: self.joker_sold,
+            },
+            run_history: self.run_history.clone(),
+            target: None,
+            ante: Some(self.ante),
+            stake: Some(self.config.stake),
+            blind: Some(self.blind),
+            full_deck: Some(self.deck.clone()),
+            deck: self.draw_pile.clone(),
+            hands_remaining: Some(self.hands_left),
+            discards_remaining: Some(self.discards_left),
+            context: Some(RoundContextInput {
+                hands_left: Some(self.hands_left),
+                discards_left: Some(self.discards_left),
+                hand_size: Some(self.config.hand_size),
+                money: Some(self.money),
+                ante: Some(self.ante),
+                blind: Some(self.blind),
+            }),
+            joker_states: self.joker_states.clone(),
+        };
+        let report = score_with_rng(&round, &ScoreOptions::default(), &mut self.rng);
+
+        self.score += report.score;
+        self.hands_left -= 1;
+        self.discarded.clear();
+        self.hands_played.push(report.poker_hand);
+        self.run_history.record(report.poker_hand);
+        self.joker_states = report.joker_states.clone();
+        self.played_this_ante.extend(played.iter().copied());
+
+        let mut events = vec![RunEvent::Played {
+            cards: played
+                .iter()
+                .map(|card| card.to_string())
+                .collect(),
+            poker_hand: report.hand_name(),
+            score: report.score,
+            total: self.score,
+            violation: report.violation.map(|violation| violation.to_string()),
+        }];
+
+        // Purple Seals paid out when they were discarded, held cards pay out if the blind ends
+        let effects = report.effects;
+        self.earn(effects.money - effects.round_end_money, &mut events);
+        self.create_consumables(effects.hand_consumables(), &mut events);
+
+        // Shattered glass cards leave the deck for the rest of the run
+        for destroyed in effects.destroyed_cards {
+            if let Some(position) = self.deck.iter().position(|card| *card == destroyed) {
+                let card = self.deck.remove(position);
+                events.push(RunEvent::CardDestroyed { card: card.to_string() });
+            }
+        }
+
+        if self.score >= self.target() {
+            self.earn(effects.round_end_money, &mut events);
+            self.create_consumables(effects.round_end_consumables, &mut events);
+            events.extend(self.defeat_blind());
+        } else if self.hands_left == 0 {
+            events.push(self.lose());
+        } else {
+            self.draw();
+            self.lose_if_out_of_cards(&mut events);
+        }
+        events
+    }
+
+    fn lose(&mut self) -> RunEvent {
+        self.status = RunStatus::Lost;
+        RunEvent::Lost {
+            ante: self.ante,
+            blind: self.blind.to_string(),
+            score: self.score,
+            target: self.target(),
+        }
+    }
+
+    /// Ends the blind as lost when hands remain but there are no cards left to play them with
+    fn lose_if_out_of_cards(&mut self, events: &mut Vec<RunEvent>) {
+        if self.hand.is_empty() && self.draw_pile.is_empty() {
+            events.push(self.lose());
+        }
+    }
+
+    fn discard(&mut self, positions: &[usize]) -> Vec<RunEvent> {
+        let discarded = self.take_cards(positions);
+        self.discards_left -= 1;
+        self.discarded.extend(discarded.iter().copied());
+        self.joker_states.discard(&self.jokers, &discarded);
+        self.draw();
+        let mut events = vec![RunEvent::Discarded {
+            cards: discarded
+                .iter()
+                .map(|card| card.to_string())
+                .collect(),
+        }];
+
+        let tarots = discarded
+            .iter()
+            .filter(|card| card.seal == Some(Seal::Purple))
+            .map(|_| CreatedConsumable::Tarot)
+            .collect();
+        self.create_consumables(tarots, &mut events);
+        self.lose_if_out_of_cards(&mut events);
+        events
+    }
+
+    fn earn(&mut self, money: i64, events: &mut Vec<RunEvent>) {
+        if money != 0 {
+            self.money += money;
+            events.push(RunEvent::Earned { money });
+        }
+    }
+
+    fn create_consumables(
+        &mut self,
+        consumables: Vec<CreatedConsumable>,
+        events: &mut Vec<RunEvent>
+    ) {
+        for created in consumables {
+            let consumable = match created {
+                CreatedConsumable::Planet(planet) => Consumable::Planet(planet),
+                CreatedConsumable::Tarot => {
+                    let tarot = TAROTS[self.rng.roll(TAROTS.len() as u32) as usize];
+                    Consumable::Tarot { tarot, targets: Vec::new() }
+                }
+            };
+            events.push(RunEvent::CreatedConsumable { consumable: consumable.to_string() });
+            self.consumables.push(consumable);
+        }
+    }
+
+    fn defeat_blind(&mut self) -> Vec<RunEvent> {
+        let mut events = vec![RunEvent::BlindDefeated {
+            ante: self.ante,
+            blind: self.blind.to_string(),
+            score: self.score,
+        }];
+
+        let payout = Payout::new(self.blind, self.config.stake, self.hands_left, self.money);
+        self.money += payout.total();
+        events.push(RunEvent::Payout {
+            blind_reward: payout.blind_reward,
+            hands: payout.hands,
+            interest: payout.interest,
+        });
+
+        match self.blind {
+            BlindType::Small => {
+                self.blind = BlindType::Big;
+            }
+            BlindType::Big => {
+                self.blind = BlindType::Boss;
+            }
+            BlindType::Boss if self.ante >= self.config.final_ante => {
+                self.status = RunStatus::Won;
+                events.push(RunEvent::Won { ante: self.ante });
+                return events;
+            }
+            BlindType::Boss => {
+                self.ante += 1;
+                self.blind = BlindType::Small;
+                self.boss_blind = BOSS_BLINDS[self.rng.roll(BOSS_BLINDS.len() as u32) as usize];
+                self.played_this_ante.clear();
+            }
+        }
+
+        self.phase = RunPhase::Shop(Shop::new(&mut self.rng));
+        events
+    }
+
+    /// Resets the hands and discards, then shuffles the whole deck and deals a new hand
+    fn start_blind(&mut self) -> Vec<RunEvent> {
+        self.hands_left = self.config.hands;
+        self.discards_left = self.config.discards;
+        self.score = 0.0;
+        self.hands_played.clear();
+        self.discarded.clear();
+        self.joker_sold = false;
+
+        self.draw_pile = self.deck.clone();
+        for i in (1..self.draw_pile.len()).rev() {
+            let j = self.rng.roll((i + 1) as u32) as usize;
+            self.draw_pile.swap(i, j);
+        }
+        self.hand.clear();
+        self.draw();
+
+        vec![RunEvent::BlindStarted {
+            ante: self.ante,
+            blind: self.blind.to_string(),
+            boss_blind: self.active_boss_blind().map(|boss_blind| boss_blind.to_string()),
+            target: self.target(),
+        }]
+    }
+
+    /// Draws up to the hand size, keeping the hand sorted from the highest rank
+    fn draw(&mut self) {
+        while self.hand.len() < self.config.hand_size {
+            let Some(card) = self.draw_pile.pop() else {
+                break;
+            };
+            self.hand.push(card);
+        }
+        self.hand.sort_by_key(|card| std::cmp::Reverse(rank_value(card.rank)));
+    }
+}
+
+impl FromStr for RunCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_ascii_whitespace();
+        let action = words.next().unwrap_or_default();
+        let positions = words
+            .map(|word| word.parse::<usize>().map_err(|_| format!("Invalid position: `{word}`")))
+            .collect::<Result<Vec<usize>, String>>()?;
+
+        match (action, positions.as_slice()) {
+            ("play", _) => Ok(RunCommand::Play(positions)),
+            ("discard", _) => Ok(RunCommand::Discard(positions)),
+            ("buy", &[position]) => Ok(RunCommand::Buy(position)),
+            ("sell", &[position]) => Ok(RunCommand::Sell(position)),
+            ("pick", &[position]) => Ok(RunCommand::Pick(position)),
+            ("reroll", &[]) => Ok(RunCommand::Reroll),
+            ("next", &[]) => Ok(RunCommand::Next),
+            ("skip", &[]) => Ok(RunCommand::Skip),
+            ("use", &[consumable, ref targets @ ..]) => {
+                Ok(RunCommand::Use { consumable, targets: targets.to_vec() })
+            }
+            _ => Err(format!("Invalid RunCommand: `{s}`")),
+        }
+    }
+}
+
+impl Display for RunEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunEvent::BlindStarted { ante, blind, boss_blind, target } => {
+                write!(f, "Ante {ante} {blind}")?;
+                if let Some(boss_blind) = boss_blind {
+                    write!(f, " ({boss_blind})")?;
+                }
+                write!(f, ", score {target} to win")
+            }
+            RunEvent::Played { cards, poker_hand, score, total, violation } => {
+                write!(f, "Played {} for {score} ({poker_hand}), {total} so far", cards.join(", "))?;
+                if let Some(violation) = violation {
+                    write!(f, ", not allowed: {violation}")?;
+                }
+                Ok(())
+            }
+            RunEvent::Discarded { cards } => write!(f, "Discarded {}", cards.join(", ")),
+            RunEvent::Used { consumable, changes } => {
+                write!(f, "Used {consumable}")?;
+                if !changes.is_empty() {
+                    write!(f, ": {}", changes.join(", "))?;
+                }
+                Ok(())
+            }
+            RunEvent::Earned { money } => write!(f, "Earned ${money}"),
+            RunEvent::CreatedConsumable { consumable } => write!(f, "Created {consumable}"),
+            RunEvent::CardDestroyed { card } => write!(f, "Destroyed {card}"),
+            RunEvent::BlindDefeated { ante, blind, score } => {
+                write!(f, "Defeated the ante {ante} {blind} with {score}")
+            }
+            RunEvent::Payout { blind_reward, hands, interest } => {
+                write!(
+                    f,
+                    "Earned ${} (${blind_reward} reward, ${hands} for hands, ${interest} interest)",
+                    blind_reward + hands + interest
+                )
+            }
+            RunEvent::Bought { item, price } => write!(f, "Bought {item} for ${price}"),
+            RunEvent::Sold { joker, money } => write!(f, "Sold {joker} for ${money}"),
+            RunEvent::Rerolled { cost } => write!(f, "Rerolled the shop for ${cost}"),
+            RunEvent::PackOpened { pack, choices } => {
+                write!(f, "Opened {pack}: {}", choices.join(", "))
+            }
+            RunEvent::Picked { item } => write!(f, "Picked {item}"),
+            RunEvent::Won { ante } => write!(f, "Won the run at ante {ante}"),
+            RunEvent::Lost { ante, blind, score, target } => {
+                write!(f, "Lost to the ante {ante} {blind}, scored {score} of {target}")
+            }
+        }
+    }
+}
+
+impl Display for RunState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let numbered = |items: &[String]| -> String {
+            items
+                .iter()
+                .enumerate()
+                .map(|(position, item)| format!("{position}: {item}"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let consumables = match self.consumables.is_empty() {
+            true => String::new(),
+            false => format!("\nConsumables: {}", numbered(&self.consumables)),
+        };
+
+        if self.phase == "shop" {
+            writeln!(f, "Shop, ${}, reroll ${}", self.money, self.reroll_cost.unwrap_or_default())?;
+            writeln!(f, "Offers: {}", numbered(&self.shop))?;
+            return write!(
+                f,
+                "Jokers ({}/{}): {}{consumables}",
+                self.jokers.len(),
+                self.joker_slots,
+                numbered(&self.jokers)
+            );
+        }
+        if self.phase == "pack" {
+            return write!(f, "Pick one: {}", numbered(&self.pack));
+        }
+
+        write!(f, "Ante {} {}", self.ante, self.blind)?;
+        if let Some(boss_blind) = &self.boss_blind {
+            write!(f, " ({boss_blind})")?;
+        }
+        writeln!(
+            f,
+            ": {} of {}, {} hands and {} discards left, ${}",
+            self.score,
+            self.target,
+            self.hands_left,
+            self.discards_left,
+            self.money
+        )?;
+        write!(f, "Hand: {}{consumables}", numbered(&self.hand))
+    }
+}
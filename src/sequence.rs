@@ -0,0 +1,176 @@
+use std::fmt::{ Display, Formatter };
+
+use ortalib::{ Chips, Mult };
+use serde::{ Deserialize, Serialize };
+
+use crate::cards::{ JokerCard, PlayingCard };
+use crate::handlevels::HandLevels;
+use crate::input::{ RoundContextInput, RoundInput, RunHistory };
+use crate::jokerstates::{ JokerState, JokerStates };
+use crate::rng::SeededRng;
+use crate::roundscorer::ScoreOptions;
+use crate::score_with_rng;
+
+/// Hands played one after another with the same jokers, such as
+///
+/// ```yaml
+/// jokers: [ Green Joker, Ice Cream ]
+/// context:
+///   hands_left: 3
+/// hands:
+///   - cards_played: [ K♥, K♠ ]
+///   - discards:
+///       - [ 2♣, 7♦ ]
+///     cards_played: [ 9♥, 9♠, 4♦, 4♣ ]
+///     cards_held_in_hand: [ A♠ ]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct HandSequence {
+    #[serde(default)]
+    pub jokers: Vec<JokerCard>,
+
+    #[serde(default)]
+    pub hand_levels: HandLevels,
+
+    /// What the jokers built up before the first hand
+    #[serde(default)]
+    pub joker_states: JokerStates,
+
+    /// What was played in the run before the first hand
+    #[serde(default)]
+    pub run_history: RunHistory,
+
+    /// Resources of the round before the first hand, each hand and discard uses one up
+    #[serde(default)]
+    pub context: Option<RoundContextInput>,
+
+    pub hands: Vec<SequenceHand>,
+}
+
+/// A single hand of a sequence
+#[derive(Debug, Clone, Deserialize)]
+pub struct SequenceHand {
+    /// Discards made before the hand is played, each one a list of cards discarded together
+    #[serde(default)]
+    pub discards: Vec<Vec<PlayingCard>>,
+
+    pub cards_played: Vec<PlayingCard>,
+
+    #[serde(default)]
+    pub cards_held_in_hand: Vec<PlayingCard>,
+}
+
+/// Score of every hand of a sequence
+#[derive(Debug, Clone, Serialize)]
+pub struct SequenceReport {
+    pub hands: Vec<SequenceHandReport>,
+    pub total_score: f64,
+}
+
+/// Score of a hand and what the jokers have built up once it was played
+#[derive(Debug, Clone, Serialize)]
+pub struct SequenceHandReport {
+    pub poker_hand: String,
+    pub chips: Chips,
+    pub mult: Mult,
+    pub score: f64,
+    pub joker_states: Vec<JokerStateSummary>,
+    /// Chips cards have gained from Hiker, keyed by card such as `K♥`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub card_chips: Vec<CardChipsSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JokerStateSummary {
+    pub joker: String,
+    #[serde(flatten)]
+    pub state: JokerState,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CardChipsSummary {
+    pub card: String,
+    pub chips: Chips,
+}
+
+/// Scores each hand in order, carrying the joker states, the hands played so far and the
+/// hands and discards left from one hand to the next
+pub fn score_sequence(sequence: &HandSequence, options: &ScoreOptions) -> SequenceReport {
+    let mut rng = SeededRng::new(options.seed);
+    let mut states = sequence.joker_states.clone();
+    let mut round = RoundInput::new(Vec::new(), Vec::new(), sequence.jokers.clone());
+    round.hand_levels = sequence.hand_levels.clone();
+    round.run_history = sequence.run_history.clone();
+    round.context = sequence.context;
+    let mut hands_left = round.context().hands_left;
+    let mut discards_left = round.context().discards_left;
+
+    let mut hands: Vec<SequenceHandReport> = Vec::new();
+    for hand in &sequence.hands {
+        hand.discards.iter().for_each(|cards| states.discard(&sequence.jokers, cards));
+        discards_left = discards_left.saturating_sub(hand.discards.len() as u32);
+        round.context = Some(RoundContextInput {
+            hands_left: Some(hands_left),
+            discards_left: Some(discards_left),
+            ..round.context.unwrap_or_default()
+        });
+
+        round.cards_played = hand.cards_played.clone();
+        round.cards_held_in_hand = hand.cards_held_in_hand.clone();
+        round.cards_discarded = hand.discards.concat();
+        round.joker_states = states;
+        let report = score_with_rng(&round, options, &mut rng);
+
+        round.history.hands_played_this_round.push(report.poker_hand);
+        round.run_history.record(report.poker_hand);
+        hands_left = hands_left.saturating_sub(1);
+        states = report.joker_states.clone();
+        hands.push(SequenceHandReport {
+            poker_hand: report.hand_name(),
+            chips: report.chips,
+            mult: report.mult,
+            score: report.score,
+            joker_states: states
+                .summary(&sequence.jokers)
+                .into_iter()
+                .map(|(joker, state)| JokerStateSummary { joker: joker.to_string(), state })
+                .collect(),
+            card_chips: states
+                .card_summary()
+                .into_iter()
+                .map(|(card, chips)| CardChipsSummary { card, chips })
+                .collect(),
+        });
+    }
+
+    SequenceReport {
+        total_score: hands
+            .iter()
+            .map(|hand| hand.score)
+            .sum(),
+        hands,
+    }
+}
+
+impl Display for SequenceReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, hand) in self.hands.iter().enumerate() {
+            writeln!(
+                f,
+                "Hand {}: {} {} x {} = {}",
+                index + 1,
+                hand.poker_hand,
+                hand.chips,
+                hand.mult,
+                hand.score
+            )?;
+            for summary in &hand.joker_states {
+                writeln!(f, "  {} {}", summary.joker, summary.state)?;
+            }
+            for card in &hand.card_chips {
+                writeln!(f, "  {} +{} Chips", card.card, card.chips)?;
+            }
+        }
+        writeln!(f, "Total score: {}", self.total_score)
+    }
+}
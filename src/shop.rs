@@ -0,0 +1,358 @@
+use std::fmt::{ Display, Formatter };
+
+use ortalib::{ Edition, Rank, Suit };
+use serde::Serialize;
+
+use crate::cards::{ Enhancement, Joker, JokerCard, PlayingCard, Seal };
+use crate::consumables::Planet;
+use crate::rng::GameRng;
+use crate::target::{ BlindType, Stake };
+
+/// Jokers and playing cards on sale at once, booster packs are on top of these
+const CARD_SLOTS: usize = 2;
+const PACK_SLOTS: usize = 2;
+
+/// Cost of the first reroll in a shop, each reroll after it costs $1 more
+const BASE_REROLL_COST: i64 = 5;
+
+/// Interest is $1 for every $5 held, up to this much
+const MAX_INTEREST: i64 = 5;
+
+/// Every joker which can appear in the shop
+const JOKERS: [Joker; 54] = {
+    use crate::cards::Joker::*;
+
+    [
+        Joker, JollyJoker, ZanyJoker, MadJoker, CrazyJoker, DrollJoker, SlyJoker, WilyJoker,
+        CleverJoker, DeviousJoker, CraftyJoker, AbstractJoker, RaisedFist, Blackboard, Baron,
+        GreedyJoker, LustyJoker, WrathfulJoker, GluttonousJoker, Fibonacci, ScaryFace, EvenSteven,
+        OddTodd, Photograph, SmileyFace, FlowerPot, FourFingers, Shortcut, Mime, Pareidolia,
+        Splash, SockAndBuskin, SmearedJoker, Blueprint, Misprint, Bloodstone, SpaceJoker,
+        RideTheBus, GreenJoker, IceCream, Runner, SquareJoker, SpareTrousers, Hiker, WeeJoker,
+        Supernova, CardSharp, Obelisk, LoyaltyCard, Banner, MysticSummit, HalfJoker, Acrobat, Dusk,
+    ]
+};
+
+/// Planets which can appear in Celestial packs, the secret hands' planets never do
+const PACK_PLANETS: [Planet; 9] = [
+    Planet::Pluto,
+    Planet::Mercury,
+    Planet::Uranus,
+    Planet::Venus,
+    Planet::Saturn,
+    Planet::Jupiter,
+    Planet::Earth,
+    Planet::Mars,
+    Planet::Neptune,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+/// Booster packs, opening one lets a single card be picked from its contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoosterPack {
+    /// 3 playing cards which join the deck
+    Standard,
+    /// 2 jokers
+    Buffoon,
+    /// 3 planets which are used straight away
+    Celestial,
+}
+
+/// Something which can be bought in the shop or picked from a pack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShopItem {
+    Joker(JokerCard),
+    PlayingCard(PlayingCard),
+    Planet(Planet),
+    Pack(BoosterPack),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShopOffer {
+    pub item: ShopItem,
+    pub price: i64,
+}
+
+/// Shop visited between blinds
+#[derive(Debug, Clone)]
+pub struct Shop {
+    /// Jokers and playing cards, followed by booster packs
+    pub offers: Vec<ShopOffer>,
+    pub reroll_cost: i64,
+}
+
+/// Money paid out for beating a blind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Payout {
+    pub blind_reward: i64,
+    /// $1 for every hand which was not needed
+    pub hands: i64,
+    pub interest: i64,
+}
+
+impl Payout {
+    pub fn new(blind: BlindType, stake: Stake, hands_left: u32, money: i64) -> Payout {
+        let blind_reward = match blind {
+            // Red Stake and above take away the small blind reward
+            BlindType::Small if stake != Stake::White => 0,
+            BlindType::Small => 3,
+            BlindType::Big => 4,
+            BlindType::Boss => 5,
+        };
+        Payout {
+            blind_reward,
+            hands: i64::from(hands_left),
+            interest: (money.max(0) / 5).min(MAX_INTEREST),
+        }
+    }
+
+    pub fn total(&self) -> i64 {
+        self.blind_reward + self.hands + self.interest
+    }
+}
+
+impl Shop {
+    pub fn new(rng: &mut dyn GameRng) -> Shop {
+        let mut shop = Shop { offers: Vec::new(), reroll_cost: BASE_REROLL_COST };
+        shop.offers.extend((0..CARD_SLOTS).map(|_| random_card_offer(rng)));
+        shop.offers.extend((0..PACK_SLOTS).map(|_| random_pack_offer(rng)));
+        shop
+    }
+
+    /// Replaces the jokers and playing cards on sale, booster packs stay
+    pub fn reroll(&mut self, rng: &mut dyn GameRng) {
+        self.offers.retain(|offer| matches!(offer.item, ShopItem::Pack(_)));
+        let cards: Vec<ShopOffer> = (0..CARD_SLOTS).map(|_| random_card_offer(rng)).collect();
+        self.offers.splice(0..0, cards);
+        self.reroll_cost += 1;
+    }
+}
+
+impl BoosterPack {
+    pub fn price(&self) -> i64 {
+        4
+    }
+
+    /// Cards to pick from once the pack is opened
+    pub fn open(&self, rng: &mut dyn GameRng) -> Vec<ShopItem> {
+        match self {
+            BoosterPack::Standard => {
+                (0..3).map(|_| ShopItem::PlayingCard(random_playing_card(rng, true))).collect()
+            }
+            BoosterPack::Buffoon => {
+                (0..2).map(|_| ShopItem::Joker(random_joker(rng))).collect()
+            }
+            BoosterPack::Celestial => {
+                (0..3)
+                    .map(|_| {
+                        ShopItem::Planet(PACK_PLANETS[rng.roll(PACK_PLANETS.len() as u32) as usize])
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Rarity and base price of each joker
+pub fn joker_rarity_and_cost(joker: Joker) -> (Rarity, i64) {
+    use crate::cards::Joker::*;
+    use Rarity::*;
+
+    #[rustfmt::skip]
+    let value = match joker {
+        Joker =>            (Common, 2),
+        JollyJoker =>       (Common, 3),
+        ZanyJoker =>        (Common, 4),
+        MadJoker =>         (Common, 4),
+        CrazyJoker =>       (Common, 4),
+        DrollJoker =>       (Common, 4),
+        SlyJoker =>         (Common, 3),
+        WilyJoker =>        (Common, 4),
+        CleverJoker =>      (Common, 4),
+        DeviousJoker =>     (Common, 4),
+        CraftyJoker =>      (Common, 4),
+        AbstractJoker =>    (Common, 4),
+        RaisedFist =>       (Common, 5),
+        Blackboard =>       (Uncommon, 6),
+        Baron =>            (Rare, 8),
+        GreedyJoker =>      (Common, 5),
+        LustyJoker =>       (Common, 5),
+        WrathfulJoker =>    (Common, 5),
+        GluttonousJoker =>  (Common, 5),
+        Fibonacci =>        (Uncommon, 8),
+        ScaryFace =>        (Common, 4),
+        EvenSteven =>       (Common, 4),
+        OddTodd =>          (Common, 4),
+        Photograph =>       (Common, 5),
+        SmileyFace =>       (Common, 4),
+        FlowerPot =>        (Uncommon, 6),
+        FourFingers =>      (Uncommon, 7),
+        Shortcut =>         (Uncommon, 7),
+        Mime =>             (Uncommon, 5),
+        Pareidolia =>       (Uncommon, 5),
+        Splash =>           (Common, 3),
+        SockAndBuskin =>    (Uncommon, 6),
+        SmearedJoker =>     (Uncommon, 7),
+        Blueprint =>        (Rare, 10),
+        Misprint =>         (Common, 4),
+        Bloodstone =>       (Uncommon, 7),
+        SpaceJoker =>       (Uncommon, 5),
+        RideTheBus =>       (Common, 6),
+        GreenJoker =>       (Common, 4),
+        IceCream =>         (Common, 5),
+        Runner =>           (Common, 5),
+        SquareJoker =>      (Common, 4),
+        SpareTrousers =>    (Uncommon, 6),
+        Hiker =>            (Uncommon, 5),
+        WeeJoker =>         (Rare, 8),
+        Supernova =>        (Common, 5),
+        CardSharp =>        (Uncommon, 6),
+        Obelisk =>          (Rare, 8),
+        LoyaltyCard =>      (Uncommon, 5),
+        Banner =>           (Common, 5),
+        MysticSummit =>     (Common, 5),
+        HalfJoker =>        (Common, 5),
+        Acrobat =>          (Uncommon, 6),
+        Dusk =>             (Uncommon, 5),
+        Custom(_) =>        (Common, 4),
+    };
+
+    value
+}
+
+/// Extra cost of an edition on a joker or playing card
+fn edition_cost(edition: Option<Edition>) -> i64 {
+    match edition {
+        None => 0,
+        Some(Edition::Foil) => 2,
+        Some(Edition::Holographic) => 3,
+        Some(Edition::Polychrome) => 5,
+    }
+}
+
+pub fn joker_cost(joker: &JokerCard) -> i64 {
+    joker_rarity_and_cost(joker.joker).1 + edition_cost(joker.edition)
+}
+
+/// Money for selling a joker, half its cost rounded down but at least $1
+pub fn sell_value(joker: &JokerCard) -> i64 {
+    (joker_cost(joker) / 2).max(1)
+}
+
+impl ShopItem {
+    pub fn price(&self) -> i64 {
+        match self {
+            ShopItem::Joker(joker) => joker_cost(joker),
+            ShopItem::PlayingCard(card) => 1 + edition_cost(card.edition),
+            ShopItem::Planet(_) => 3,
+            ShopItem::Pack(pack) => pack.price(),
+        }
+    }
+}
+
+/// A joker from the whole pool, 70% common, 25% uncommon and 5% rare
+pub fn random_joker(rng: &mut dyn GameRng) -> JokerCard {
+    let rarity = match rng.roll(100) {
+        0..70 => Rarity::Common,
+        70..95 => Rarity::Uncommon,
+        _ => Rarity::Rare,
+    };
+    let pool: Vec<Joker> = JOKERS.iter()
+        .copied()
+        .filter(|&joker| joker_rarity_and_cost(joker).0 == rarity)
+        .collect();
+    let joker = pool[rng.roll(pool.len() as u32) as usize];
+    JokerCard::new(joker, random_edition(rng))
+}
+
+/// Foil 2%, Holographic 1.4% and Polychrome 0.3% of the time
+fn random_edition(rng: &mut dyn GameRng) -> Option<Edition> {
+    match rng.roll(1000) {
+        0..3 => Some(Edition::Polychrome),
+        3..17 => Some(Edition::Holographic),
+        17..37 => Some(Edition::Foil),
+        _ => None,
+    }
+}
+
+/// A random playing card, `enhanced` ones may also get an enhancement, edition or seal
+pub fn random_playing_card(rng: &mut dyn GameRng, enhanced: bool) -> PlayingCard {
+    use Rank::*;
+
+    let ranks = [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace];
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+    let rank = ranks[rng.roll(ranks.len() as u32) as usize];
+    let suit = suits[rng.roll(suits.len() as u32) as usize];
+    let mut card = PlayingCard::new(rank, suit);
+    if !enhanced {
+        return card;
+    }
+
+    if rng.chance(2, 5) {
+        let enhancements = [
+            Enhancement::Bonus,
+            Enhancement::Mult,
+            Enhancement::Wild,
+            Enhancement::Glass,
+            Enhancement::Steel,
+            Enhancement::Stone,
+            Enhancement::Gold,
+            Enhancement::Lucky,
+        ];
+        card.enhancement = Some(enhancements[rng.roll(enhancements.len() as u32) as usize]);
+    }
+    card.edition = random_edition(rng);
+    if rng.chance(1, 5) {
+        let seals = [Seal::Red, Seal::Gold, Seal::Blue, Seal::Purple];
+        card.seal = Some(seals[rng.roll(seals.len() as u32) as usize]);
+    }
+    card
+}
+
+/// Jokers are on sale five times as often as playing cards
+fn random_card_offer(rng: &mut dyn GameRng) -> ShopOffer {
+    let item = match rng.chance(5, 6) {
+        true => ShopItem::Joker(random_joker(rng)),
+        false => ShopItem::PlayingCard(random_playing_card(rng, false)),
+    };
+    ShopOffer { item, price: item.price() }
+}
+
+fn random_pack_offer(rng: &mut dyn GameRng) -> ShopOffer {
+    let packs = [BoosterPack::Standard, BoosterPack::Buffoon, BoosterPack::Celestial];
+    let item = ShopItem::Pack(packs[rng.roll(packs.len() as u32) as usize]);
+    ShopOffer { item, price: item.price() }
+}
+
+impl Display for BoosterPack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoosterPack::Standard => write!(f, "Standard Pack"),
+            BoosterPack::Buffoon => write!(f, "Buffoon Pack"),
+            BoosterPack::Celestial => write!(f, "Celestial Pack"),
+        }
+    }
+}
+
+impl Display for ShopItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShopItem::Joker(joker) => write!(f, "{}", joker),
+            ShopItem::PlayingCard(card) => write!(f, "{}", card),
+            ShopItem::Planet(planet) => write!(f, "{}", planet),
+            ShopItem::Pack(pack) => write!(f, "{}", pack),
+        }
+    }
+}
+
+impl Display for ShopOffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ${}", self.item, self.price)
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::{ Display, Formatter };
+
+use serde::Serialize;
+
+use crate::input::RoundInput;
+use crate::rng::SeededRng;
+use crate::roundscorer::ScoreOptions;
+use crate::score_with_rng;
+
+/// Percentiles reported by a simulation
+const PERCENTILES: [u32; 5] = [5, 25, 50, 75, 95];
+
+/// Number of equal width histogram bins
+const HISTOGRAM_BINS: usize = 10;
+
+/// Summary of the final scores of many seeded runs of the same round
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub runs: usize,
+    pub seed: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<HistogramBin>,
+    /// Fraction of runs which beat the blind target, if there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pass_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Percentile {
+    pub percentile: u32,
+    pub score: f64,
+}
+
+/// Runs which scored in `low..high`, the last bin also includes `high`
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct HistogramBin {
+    pub low: f64,
+    pub high: f64,
+    pub count: usize,
+}
+
+/// Scores the round `runs` times, each run continuing the random rolls of the one before
+pub fn simulate(round: &RoundInput, options: &ScoreOptions, runs: usize) -> SimulationReport {
+    let mut rng = SeededRng::new(options.seed);
+    let options = ScoreOptions { explain: false, ..options.clone() };
+
+    let mut scores: Vec<f64> = Vec::with_capacity(runs);
+    let mut passed: usize = 0;
+    let mut has_target = false;
+    for _ in 0..runs {
+        let report = score_with_rng(round, &options, &mut rng);
+        if let Some(outcome) = report.outcome {
+            has_target = true;
+            passed += usize::from(outcome.passed);
+        }
+        scores.push(report.score);
+    }
+    scores.sort_by(f64::total_cmp);
+
+    let runs = scores.len().max(1);
+    let min = scores.first().copied().unwrap_or(0.0);
+    let max = scores.last().copied().unwrap_or(0.0);
+    SimulationReport {
+        runs: scores.len(),
+        seed: options.seed,
+        mean: scores.iter().sum::<f64>() / (runs as f64),
+        median: median(&scores),
+        min,
+        max,
+        percentiles: PERCENTILES.iter()
+            .map(|&percentile| Percentile { percentile, score: nearest_rank(&scores, percentile) })
+            .collect(),
+        histogram: histogram(&scores, min, max),
+        pass_rate: has_target.then(|| (passed as f64) / (runs as f64)),
+    }
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        len if len % 2 == 0 => (sorted[len / 2 - 1] + sorted[len / 2]) / 2.0,
+        len => sorted[len / 2],
+    }
+}
+
+/// Smallest score which at least `percentile` percent of runs are at or below
+fn nearest_rank(sorted: &[f64], percentile: u32) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((f64::from(percentile) / 100.0) * (sorted.len() as f64)).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn histogram(sorted: &[f64], min: f64, max: f64) -> Vec<HistogramBin> {
+    if sorted.is_empty() {
+        return Vec::new();
+    }
+    if min == max {
+        return vec![HistogramBin { low: min, high: max, count: sorted.len() }];
+    }
+
+    let width = (max - min) / (HISTOGRAM_BINS as f64);
+    let mut bins: Vec<HistogramBin> = (0..HISTOGRAM_BINS)
+        .map(|index| HistogramBin {
+            low: min + width * (index as f64),
+            high: min + width * ((index + 1) as f64),
+            count: 0,
+        })
+        .collect();
+
+    sorted.iter().for_each(|score| {
+        let index = (((score - min) / width) as usize).min(HISTOGRAM_BINS - 1);
+        bins[index].count += 1;
+    });
+    bins
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Runs: {} (seed {})", self.runs, self.seed)?;
+        writeln!(f, "Mean: {:.2}", self.mean)?;
+        writeln!(f, "Median: {}", self.median)?;
+        writeln!(f, "Min: {}", self.min)?;
+        writeln!(f, "Max: {}", self.max)?;
+        for percentile in &self.percentiles {
+            writeln!(f, "P{}: {}", percentile.percentile, percentile.score)?;
+        }
+        if let Some(pass_rate) = self.pass_rate {
+            writeln!(f, "Beats target: {:.2}%", pass_rate * 100.0)?;
+        }
+
+        writeln!(f, "Histogram:")?;
+        let largest = self.histogram
+            .iter()
+            .map(|bin| bin.count)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for bin in &self.histogram {
+            let bar = "#".repeat((bin.count * 40).div_ceil(largest));
+            writeln!(f, "  {:>12.0} - {:<12.0} {:>6} {}", bin.low, bin.high, bin.count, bar)?;
+        }
+        Ok(())
+    }
+}
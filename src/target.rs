@@ -0,0 +1,184 @@
+use std::fmt::{ Display, Formatter };
+use std::str::FromStr;
+
+use ortalib::{ Chips, Mult };
+use serde::{ Deserialize, Serialize };
+
+/// Stake the run is played on, higher stakes scale blind targets faster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Stake {
+    #[default]
+    White,
+    Red,
+    Green,
+    Black,
+    Blue,
+    Purple,
+    Orange,
+    Gold,
+}
+
+/// Which blind of the ante is being played
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum BlindType {
+    Small,
+    Big,
+    Boss,
+}
+
+/// Whether a score beats the blind target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TargetOutcome {
+    pub target: f64,
+    pub score: f64,
+    pub passed: bool,
+    /// Overshoot when positive, deficit when negative
+    pub margin: f64,
+}
+
+impl TargetOutcome {
+    pub fn new(chips: Chips, mult: Mult, target: f64) -> TargetOutcome {
+        let score = (chips * mult).floor();
+        TargetOutcome { target, score, passed: score >= target, margin: score - target }
+    }
+}
+
+impl Stake {
+    /// How fast blind targets grow with the ante
+    fn scaling(&self) -> usize {
+        match self {
+            Stake::White | Stake::Red => 1,
+            Stake::Green | Stake::Black | Stake::Blue => 2,
+            Stake::Purple | Stake::Orange | Stake::Gold => 3,
+        }
+    }
+}
+
+impl BlindType {
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            BlindType::Small => 1.0,
+            BlindType::Big => 1.5,
+            BlindType::Boss => 2.0,
+        }
+    }
+}
+
+/// Base chips needed for a small blind at the given ante
+pub fn ante_base_chips(ante: u32, stake: Stake) -> f64 {
+    #[rustfmt::skip]
+    let amounts: [f64; 8] = match stake.scaling() {
+        1 => [300.0, 800.0, 2000.0, 5000.0, 11000.0, 20000.0, 35000.0, 50000.0],
+        2 => [300.0, 900.0, 2600.0, 8000.0, 20000.0, 36000.0, 60000.0, 100000.0],
+        _ => [300.0, 1000.0, 3200.0, 9000.0, 25000.0, 60000.0, 110000.0, 200000.0],
+    };
+
+    if ante < 1 {
+        return 100.0;
+    }
+    if ante <= 8 {
+        return amounts[(ante - 1) as usize];
+    }
+
+    // Endless mode, grows faster than exponential and keeps two significant digits
+    let extra = f64::from(ante - 8);
+    let growth = 0.75 * extra;
+    let amount = amounts[7] * (1.6 + growth.powf(1.0 + 0.2 * extra)).powf(extra);
+    let magnitude = (10f64).powf(amount.log10().floor() - 1.0);
+    (amount / magnitude).floor() * magnitude
+}
+
+/// Chips needed to beat a blind
+pub fn blind_target(ante: u32, stake: Stake, blind: BlindType) -> f64 {
+    (ante_base_chips(ante, stake) * blind.multiplier()).floor()
+}
+
+impl Display for TargetOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.passed {
+            write!(f, "Beats the blind target of {} by {}", self.target, self.margin)
+        } else {
+            write!(f, "Short of the blind target of {} by {}", self.target, -self.margin)
+        }
+    }
+}
+
+impl Display for Stake {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Stake::White => "White",
+            Stake::Red => "Red",
+            Stake::Green => "Green",
+            Stake::Black => "Black",
+            Stake::Blue => "Blue",
+            Stake::Purple => "Purple",
+            Stake::Orange => "Orange",
+            Stake::Gold => "Gold",
+        };
+        write!(f, "{name} Stake")
+    }
+}
+
+impl Display for BlindType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlindType::Small => write!(f, "Small Blind"),
+            BlindType::Big => write!(f, "Big Blind"),
+            BlindType::Boss => write!(f, "Boss Blind"),
+        }
+    }
+}
+
+impl FromStr for Stake {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Stake::*;
+
+        #[rustfmt::skip]
+        let value = match s.strip_suffix(" Stake").unwrap_or(s) {
+            "White" =>  White,
+            "Red" =>    Red,
+            "Green" =>  Green,
+            "Black" =>  Black,
+            "Blue" =>   Blue,
+            "Purple" => Purple,
+            "Orange" => Orange,
+            "Gold" =>   Gold,
+            _ => return Err(format!("Invalid Stake: `{s}`")),
+        };
+
+        Ok(value)
+    }
+}
+
+impl FromStr for BlindType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_suffix(" Blind").unwrap_or(s) {
+            "Small" => Ok(BlindType::Small),
+            "Big" => Ok(BlindType::Big),
+            "Boss" => Ok(BlindType::Boss),
+            _ => Err(format!("Invalid BlindType: `{s}`")),
+        }
+    }
+}
+
+impl TryFrom<String> for Stake {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for BlindType {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
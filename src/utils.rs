@@ -1,4 +1,5 @@
-use ortalib::Rank;
+use ortalib::{ PokerHand, Rank };
+use serde::{ Deserialize, Deserializer, de::Error };
 
 pub fn rank_value(rank: Rank) -> u8 {
     match rank {
@@ -17,3 +18,36 @@ pub fn rank_value(rank: Rank) -> u8 {
         Rank::Ace => 14, // Ace high by default
     }
 }
+
+/// Every poker hand, weakest first
+pub const POKER_HANDS: [PokerHand; 12] = [
+    PokerHand::HighCard,
+    PokerHand::Pair,
+    PokerHand::TwoPair,
+    PokerHand::ThreeOfAKind,
+    PokerHand::Straight,
+    PokerHand::Flush,
+    PokerHand::FullHouse,
+    PokerHand::FourOfAKind,
+    PokerHand::StraightFlush,
+    PokerHand::FiveOfAKind,
+    PokerHand::FlushHouse,
+    PokerHand::FlushFive,
+];
+
+/// Parses a poker hand from its display name, such as `Three Of A Kind`
+pub fn parse_poker_hand(name: &str) -> Result<PokerHand, String> {
+    POKER_HANDS.into_iter()
+        .find(|hand| hand.to_string().eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("Invalid PokerHand: `{name}`"))
+}
+
+/// Deserializes a list of poker hands from their display names
+pub fn deserialize_poker_hands<'de, D>(deserializer: D) -> Result<Vec<PokerHand>, D::Error>
+    where D: Deserializer<'de>
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|name| parse_poker_hand(name).map_err(D::Error::custom))
+        .collect()
+}
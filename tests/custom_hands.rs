@@ -0,0 +1,34 @@
+use ortalab::custom_hands::CustomHandDefinition;
+
+fn check(pattern: &str) -> Result<(), String> {
+    let definition: CustomHandDefinition = serde_yaml
+        ::from_str(&format!("{{ name: Odd Hand, chips: 50, mult: 5, above: Pair, {pattern} }}"))
+        .unwrap();
+    definition.to_hand().map(|_| ())
+}
+
+#[test]
+fn rank_groups_need_a_group() {
+    assert_eq!(check("pattern: { rank_groups: [] }"), Err("Odd Hand has no rank groups".into()));
+}
+
+#[test]
+fn rank_groups_need_cards_in_every_group() {
+    assert_eq!(
+        check("pattern: { rank_groups: [3, 0] }"),
+        Err("Odd Hand has a rank group of size 0".into())
+    );
+}
+
+#[test]
+fn distinct_suits_are_between_one_and_four() {
+    assert_eq!(
+        check("pattern: { distinct_suits: 0 }"),
+        Err("Odd Hand needs 1 to 4 distinct suits, not 0".into())
+    );
+    assert_eq!(
+        check("pattern: { distinct_suits: 5 }"),
+        Err("Odd Hand needs 1 to 4 distinct suits, not 5".into())
+    );
+    assert_eq!(check("pattern: { distinct_suits: 4 }"), Ok(()));
+}
@@ -0,0 +1,20 @@
+use ortalab::cards::Joker;
+use ortalab::custom_jokers::register_custom_jokers;
+
+#[test]
+fn a_built_in_name_registers_none_of_the_jokers() {
+    let definitions = "
+        jokers:
+          - name: Lucky Eight
+            trigger: on_scored_card
+            conditions: { rank: '8' }
+            effect: { mult: 8 }
+          - name: Joker
+            trigger: independent
+            effect: { mult: 4 }
+    ";
+
+    let error = register_custom_jokers(definitions).unwrap_err();
+    assert_eq!(error, "Joker `Joker` already exists");
+    assert!("Lucky Eight".parse::<Joker>().is_err());
+}
@@ -0,0 +1,146 @@
+use ortalab::{ DiscardAdvice, RoundInput, ScoreOptions, advise_discard };
+
+fn advise(yaml: &str, top: usize, draws: usize) -> DiscardAdvice {
+    let round: RoundInput = serde_yaml::from_str(yaml).unwrap();
+    advise_discard(&round, &ScoreOptions::default(), top, draws).unwrap()
+}
+
+#[test]
+fn keeping_the_hand_is_scored_once() {
+    let advice = advise(
+        "
+        cards_played: [ K♥, Q♠, J♦, 10♣, 9♥ ]
+        cards_held_in_hand: [ 2♦ ]
+        ",
+        5,
+        10
+    );
+
+    assert!(advice.keep.discard.is_empty());
+    assert_eq!(advice.keep.draws, 1);
+    assert!(advice.keep.exact);
+    // Straight, (30 + 10 + 10 + 10 + 10 + 9) x 4
+    assert_eq!(advice.keep.expected_score, 316.0);
+}
+
+#[test]
+fn candidates_are_sorted_by_expected_score() {
+    let advice = advise(
+        "
+        cards_played: [ K♥, Q♠, 7♦, 4♣, 2♥ ]
+        cards_held_in_hand: [ 9♦, 5♠ ]
+        ",
+        20,
+        8
+    );
+
+    assert_eq!(advice.candidates.len(), 20);
+    assert!(
+        advice.candidates
+            .windows(2)
+            .all(|pair| pair[0].expected_score >= pair[1].expected_score)
+    );
+}
+
+#[test]
+fn draws_are_enumerated_when_there_are_few_enough() {
+    let advice = advise(
+        "
+        cards_played: [ 2♣, 3♦, 4♥, 5♠, K♥ ]
+        deck: [ 6♣, 9♦, J♠ ]
+        ",
+        50,
+        2
+    );
+
+    // A deck of 3 has 3 draws for 1 or 2 discarded cards and a single draw for 3
+    let possible_draws = |discarded: usize| if discarded == 3 { 1 } else { 3 };
+    assert_eq!(advice.candidates.len(), advice.candidates_considered);
+    for candidate in &advice.candidates {
+        let possible = possible_draws(candidate.discard.len());
+        assert_eq!(candidate.exact, possible <= 2, "{candidate:?}");
+        assert_eq!(candidate.draws, if candidate.exact { possible } else { 2 });
+    }
+}
+
+#[test]
+fn discarding_into_a_straight_is_best() {
+    let advice = advise(
+        "
+        cards_played: [ 2♣, 3♦, 4♥, 5♠, K♥ ]
+        deck: [ 6♣ ]
+        ",
+        1,
+        50
+    );
+
+    let best = &advice.candidates[0];
+    assert_eq!(best.discard, vec!["K♥"]);
+    // Straight, (30 + 2 + 3 + 4 + 5 + 6) x 4
+    assert_eq!(best.expected_score, 200.0);
+    assert_eq!(best.hand_probabilities[0].poker_hand, "Straight");
+    assert!(best.exact);
+}
+
+#[test]
+fn discarding_uses_up_a_discard() {
+    let advice = advise(
+        "
+        cards_played: [ K♥ ]
+        cards_held_in_hand: [ 2♣ ]
+        jokers: [ Banner ]
+        context: { discards_left: 1 }
+        deck: [ 3♦ ]
+        ",
+        5,
+        50
+    );
+
+    // Banner gives +30 Chips for the discard left, which is gone after discarding
+    assert_eq!(advice.keep.expected_score, 45.0);
+    let discard_two = advice.candidates
+        .iter()
+        .find(|candidate| candidate.discard == vec!["2♣"])
+        .unwrap();
+    assert_eq!(discard_two.expected_score, 15.0);
+}
+
+#[test]
+fn discarding_runs_the_discard_jokers() {
+    let advice = advise(
+        "
+        cards_played: [ K♥ ]
+        cards_held_in_hand: [ 2♣ ]
+        jokers: [ Green Joker ]
+        joker_states:
+          jokers: { Green Joker: { mult: 3 } }
+        deck: [ 3♦ ]
+        ",
+        5,
+        50
+    );
+
+    // Green Joker gains 1 Mult for the hand and loses 1 for the discard, so K♥ scores
+    // 15 x (1 + 3 + 1) without discarding and 15 x (1 + 3 - 1 + 1) after
+    assert_eq!(advice.keep.expected_score, 75.0);
+    let discard_two = advice.candidates
+        .iter()
+        .find(|candidate| candidate.discard == vec!["2♣"])
+        .unwrap();
+    assert_eq!(discard_two.expected_score, 60.0);
+}
+
+#[test]
+fn no_discards_left_leaves_only_keeping() {
+    let advice = advise(
+        "
+        cards_played: [ K♥, Q♠ ]
+        context: { discards_left: 0 }
+        ",
+        5,
+        50
+    );
+
+    assert!(advice.candidates.is_empty());
+    assert_eq!(advice.candidates_considered, 0);
+}
@@ -0,0 +1,36 @@
+use ortalab::{ RoundInput, ScoreOptions, exact_distribution };
+
+fn round(yaml: &str) -> RoundInput {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn lucky_card_scores_its_mult_one_time_in_five() {
+    let distribution = exact_distribution(
+        &round("{ cards_played: [ K♥ Lucky ] }"),
+        &ScoreOptions::default(),
+        100
+    ).unwrap();
+
+    // The $20 roll does not change the score so it is not branched on
+    assert_eq!(distribution.branches, 2);
+    let outcomes: Vec<(f64, f64)> = distribution.outcomes
+        .iter()
+        .map(|outcome| (outcome.score, outcome.probability))
+        .collect();
+    assert_eq!(outcomes, vec![(15.0, 0.8), (315.0, 0.2)]);
+}
+
+#[test]
+fn too_many_branches_names_the_limit() {
+    let error = exact_distribution(
+        &round("{ cards_played: [ K♥ Lucky, K♠ Lucky, K♦ Lucky ] }"),
+        &ScoreOptions::default(),
+        4
+    ).unwrap_err();
+
+    assert_eq!(
+        error,
+        "Round has more than 4 random branches, raise --max-branches or use --simulate instead"
+    );
+}
@@ -0,0 +1,180 @@
+use ortalab::cards::{ JokerCard, PlayingCard };
+use ortalab::{ Run, RunCommand, RunConfig, RunEvent, RunStatus };
+
+/// A run whose deck is only A♠, so five cards always make a Flush Five of 3440
+fn aces_run(config: RunConfig) -> Run {
+    let deck = vec!["A♠".parse().unwrap(); 20];
+    Run::new(RunConfig { seed: Some(7), deck: Some(deck), ..config }).0
+}
+
+fn play_five(run: &mut Run) -> Vec<RunEvent> {
+    run.apply(RunCommand::Play(vec![0, 1, 2, 3, 4])).unwrap()
+}
+
+fn payout(events: &[RunEvent]) -> (i64, i64, i64) {
+    events
+        .iter()
+        .find_map(|event| match event {
+            RunEvent::Payout { blind_reward, hands, interest } => {
+                Some((*blind_reward, *hands, *interest))
+            }
+            _ => None,
+        })
+        .unwrap()
+}
+
+/// Jokers on sale, by their position in the shop
+fn joker_offers(run: &Run) -> Vec<usize> {
+    run.state()
+        .shop
+        .iter()
+        .enumerate()
+        .filter(|(_, offer)| {
+            let (item, _) = offer.rsplit_once(" $").unwrap();
+            item.parse::<JokerCard>().is_ok()
+        })
+        .map(|(position, _)| position)
+        .collect()
+}
+
+#[test]
+fn beating_each_blind_pays_its_reward_hands_and_interest() {
+    let mut run = aces_run(RunConfig::default());
+
+    // $4 to start, $3 for the small blind, $1 for each of the 3 hands left and no interest
+    assert_eq!(payout(&play_five(&mut run)), (3, 3, 0));
+    assert_eq!(run.money(), 10);
+
+    run.apply(RunCommand::Next).unwrap();
+    assert_eq!(payout(&play_five(&mut run)), (4, 3, 2));
+    assert_eq!(run.money(), 19);
+
+    run.apply(RunCommand::Next).unwrap();
+    assert_eq!(payout(&play_five(&mut run)), (5, 3, 3));
+    assert_eq!(run.money(), 30);
+}
+
+#[test]
+fn interest_is_capped_at_five_dollars() {
+    let mut run = aces_run(RunConfig { money: 100, ..RunConfig::default() });
+
+    assert_eq!(payout(&play_five(&mut run)), (3, 3, 5));
+    assert_eq!(run.money(), 111);
+}
+
+#[test]
+fn rerolling_costs_a_dollar_more_each_time() {
+    let mut run = aces_run(RunConfig { money: 100, ..RunConfig::default() });
+    play_five(&mut run);
+    let money = run.money();
+
+    let costs: Vec<i64> = (0..3)
+        .map(|_| {
+            match run.apply(RunCommand::Reroll).unwrap().as_slice() {
+                [RunEvent::Rerolled { cost }] => *cost,
+                events => panic!("unexpected events {events:?}"),
+            }
+        })
+        .collect();
+
+    assert_eq!(costs, vec![5, 6, 7]);
+    assert_eq!(run.money(), money - 18);
+    assert_eq!(run.state().reroll_cost, Some(8));
+}
+
+#[test]
+fn jokers_cannot_be_bought_without_a_free_slot() {
+    let config = RunConfig {
+        money: 100,
+        joker_slots: 1,
+        jokers: vec!["Joker".parse().unwrap()],
+        ..RunConfig::default()
+    };
+    let mut run = aces_run(config);
+    play_five(&mut run);
+    while joker_offers(&run).is_empty() {
+        run.apply(RunCommand::Reroll).unwrap();
+    }
+
+    let money = run.money();
+    let error = run.apply(RunCommand::Buy(joker_offers(&run)[0])).unwrap_err();
+    assert_eq!(error, "No free joker slots, 1 are in use");
+    assert_eq!(run.money(), money);
+    assert_eq!(run.jokers().len(), 1);
+}
+
+#[test]
+fn negative_jokers_take_no_slot() {
+    let config = RunConfig {
+        money: 100,
+        joker_slots: 1,
+        jokers: vec!["Joker Negative".parse().unwrap()],
+        ..RunConfig::default()
+    };
+    let mut run = aces_run(config);
+    play_five(&mut run);
+    while joker_offers(&run).is_empty() {
+        run.apply(RunCommand::Reroll).unwrap();
+    }
+
+    run.apply(RunCommand::Buy(joker_offers(&run)[0])).unwrap();
+    assert_eq!(run.jokers().len(), 2);
+}
+
+#[test]
+fn the_same_seed_and_commands_replay_the_same_run() {
+    let commands = ["discard 0 1", "play 0 1 2 3 4", "play 0 1 2 3 4", "play 0 1", "play 0"];
+    let replay = |seed: u64| {
+        let config = RunConfig { seed: Some(seed), ..RunConfig::default() };
+        let (mut run, mut events) = Run::new(config);
+        for command in commands {
+            if run.status() != RunStatus::Playing {
+                break;
+            }
+            events.extend(run.apply(command.parse().unwrap()).unwrap());
+        }
+        let events: Vec<String> = events
+            .iter()
+            .map(|event| event.to_string())
+            .collect();
+        (events, serde_json::to_string(&run.state()).unwrap())
+    };
+
+    assert_eq!(replay(42), replay(42));
+    assert_ne!(replay(42), replay(43));
+}
+
+#[test]
+fn a_shattered_glass_card_leaves_the_deck_and_not_its_lookalike() {
+    let foil: PlayingCard = "K♥ Glass Foil".parse().unwrap();
+    let plain: PlayingCard = "K♥ Glass".parse().unwrap();
+    let mut deck = vec![foil; 8];
+    deck.extend([plain; 8]);
+
+    // Glass cards shatter 1 in 4 times, so some seed breaks one of the foil cards played
+    let destroyed = (0..100)
+        .find_map(|seed| {
+            let config = RunConfig {
+                seed: Some(seed),
+                deck: Some(deck.clone()),
+                ..RunConfig::default()
+            };
+            let (mut run, _) = Run::new(config);
+            let played: Vec<usize> = (0..run.hand().len())
+                .filter(|&position| run.hand()[position] == foil)
+                .take(5)
+                .collect();
+            let events = run.apply(RunCommand::Play(played)).unwrap();
+            let destroyed: Vec<String> = events
+                .iter()
+                .filter_map(|event| match event {
+                    RunEvent::CardDestroyed { card } => Some(card.clone()),
+                    _ => None,
+                })
+                .collect();
+            (!destroyed.is_empty()).then_some(destroyed)
+        })
+        .unwrap();
+
+    assert!(destroyed.iter().all(|card| *card == foil.to_string()), "{destroyed:?}");
+}
@@ -0,0 +1,289 @@
+use ortalab::{ RoundInput, ScoreOptions, ScoreReport, score_input };
+
+fn score_with_seed(yaml: &str, seed: u64) -> ScoreReport {
+    let round: RoundInput = serde_yaml::from_str(yaml).unwrap();
+    score_input(&round, &ScoreOptions { seed, ..ScoreOptions::default() })
+}
+
+fn score(yaml: &str) -> f64 {
+    score_with_seed(yaml, 0).score
+}
+
+// Jokers
+
+#[test]
+fn joker_adds_mult() {
+    // (5 + 10) x (1 + 4)
+    assert_eq!(score("{ cards_played: [ K♥ ], jokers: [ Joker ] }"), 75.0);
+}
+
+#[test]
+fn foil_joker_adds_chips() {
+    // (5 + 10 + 50) x (1 + 4)
+    assert_eq!(score("{ cards_played: [ K♥ ], jokers: [ Joker Foil ] }"), 325.0);
+}
+
+#[test]
+fn blueprint_copies_the_joker_to_its_right() {
+    // (5 + 10) x (1 + 4 + 4)
+    assert_eq!(score("{ cards_played: [ K♥ ], jokers: [ Blueprint, Joker ] }"), 135.0);
+}
+
+#[test]
+fn baron_multiplies_for_each_king_held() {
+    let yaml = "
+        cards_played: [ 2♠ ]
+        cards_held_in_hand: [ K♠, K♣ ]
+        jokers: [ Baron ]
+    ";
+
+    // (5 + 2) x 1.5 x 1.5, rounded down
+    assert_eq!(score(yaml), 15.0);
+}
+
+#[test]
+fn example_round_scores_as_before() {
+    assert_eq!(score(include_str!("../example.yml")), 22108.0);
+}
+
+// Boss blinds
+
+#[test]
+fn the_club_debuffs_clubs() {
+    // Pair, (10 + 10) x 2 without the K♣
+    assert_eq!(score("{ cards_played: [ K♣, K♥ ], boss_blind: The Club }"), 40.0);
+}
+
+#[test]
+fn the_flint_halves_the_base_chips_and_mult() {
+    // Pair, (5 + 10 + 10) x 1
+    assert_eq!(score("{ cards_played: [ K♣, K♥ ], boss_blind: The Flint }"), 25.0);
+}
+
+#[test]
+fn the_arm_levels_down_the_hand() {
+    let yaml = "
+        cards_played: [ K♣, K♥ ]
+        hand_levels: { Pair: 2 }
+        boss_blind: The Arm
+    ";
+
+    // Pair at level 1, (10 + 10 + 10) x 2
+    assert_eq!(score(yaml), 60.0);
+}
+
+#[test]
+fn the_eye_scores_nothing_for_a_repeated_hand() {
+    let yaml = "
+        cards_played: [ K♣, K♥ ]
+        boss_blind: The Eye
+        history: { hands_played_this_round: [ Pair ] }
+    ";
+
+    let report = score_with_seed(yaml, 0);
+    assert_eq!(report.score, 0.0);
+    assert!(report.violation.is_some());
+}
+
+#[test]
+fn the_psychic_scores_nothing_for_fewer_than_five_cards() {
+    let report = score_with_seed("{ cards_played: [ K♣, K♥ ], boss_blind: The Psychic }", 0);
+
+    assert_eq!(report.score, 0.0);
+    assert!(report.violation.is_some());
+}
+
+#[test]
+fn crimson_heart_disables_the_first_joker() {
+    let yaml = "
+        cards_played: [ K♥ ]
+        jokers: [ Joker, Greedy Joker ]
+        boss_blind: Crimson Heart
+    ";
+
+    // Greedy Joker only counts Diamonds, so the disabled Joker leaves (5 + 10) x 1
+    assert_eq!(score(yaml), 15.0);
+}
+
+#[test]
+fn verdant_leaf_debuffs_cards_until_a_joker_is_sold() {
+    let yaml = "
+        cards_played: [ K♥ ]
+        jokers: [ Joker ]
+        boss_blind: Verdant Leaf
+    ";
+    let sold = "
+        cards_played: [ K♥ ]
+        jokers: [ Joker ]
+        boss_blind: Verdant Leaf
+        history: { joker_sold: true }
+    ";
+
+    // 5 x (1 + 4) with the K♥ debuffed, then (5 + 10) x (1 + 4)
+    assert_eq!(score(yaml), 25.0);
+    assert_eq!(score(sold), 75.0);
+}
+
+// Enhancements
+
+#[test]
+fn stone_cards_always_score_fifty_chips() {
+    // High Card, (5 + 50) x 1
+    assert_eq!(score("{ cards_played: [ K♥ Stone ] }"), 55.0);
+}
+
+#[test]
+fn glass_cards_double_the_mult() {
+    // (5 + 10) x 2
+    assert_eq!(score("{ cards_played: [ K♥ Glass ] }"), 30.0);
+}
+
+#[test]
+fn steel_cards_held_multiply_the_mult() {
+    // (5 + 10) x 1.5, rounded down
+    assert_eq!(score("{ cards_played: [ K♥ ], cards_held_in_hand: [ 2♠ Steel ] }"), 22.0);
+}
+
+#[test]
+fn lucky_cards_roll_from_the_seed() {
+    let yaml = "{ cards_played: [ K♥ Lucky ] }";
+
+    // (5 + 10) x (1 + 20) along with $20 when both rolls succeed
+    let lucky = score_with_seed(yaml, 0);
+    assert_eq!(lucky.score, 315.0);
+    assert_eq!(lucky.effects.money, 20);
+    assert_eq!(score_with_seed(yaml, 3).score, 15.0);
+    assert_eq!(score_with_seed(yaml, 0).score, lucky.score);
+}
+
+// Tarots, spectrals and planets
+
+#[test]
+fn planets_level_up_the_hand() {
+    // High Card at level 2, (15 + 10) x 2
+    assert_eq!(score("{ cards_played: [ K♥ ], consumables: [ Pluto ] }"), 50.0);
+}
+
+#[test]
+fn the_empress_makes_a_mult_card() {
+    // (5 + 10) x (1 + 4)
+    assert_eq!(score("{ cards_played: [ K♥ ], consumables: [ The Empress 0 ] }"), 75.0);
+}
+
+#[test]
+fn strength_raises_the_rank() {
+    // The K♥ becomes an A♥, 5 + 11
+    assert_eq!(score("{ cards_played: [ K♥ ], consumables: [ Strength 0 ] }"), 16.0);
+}
+
+#[test]
+fn death_copies_the_second_card_onto_the_first() {
+    // Pair of 2♣, (10 + 2 + 2) x 2
+    assert_eq!(score("{ cards_played: [ K♥, 2♣ ], consumables: [ Death 0 1 ] }"), 28.0);
+}
+
+#[test]
+fn deja_vu_adds_a_red_seal() {
+    // The K♥ scores twice, 5 + 10 + 10
+    assert_eq!(score("{ cards_played: [ K♥ ], consumables: [ Deja Vu 0 ] }"), 25.0);
+}
+
+#[test]
+fn sigil_turns_the_hand_into_a_flush() {
+    let yaml = "{ cards_played: [ 2♠, 5♥, 7♦, 9♣, K♥ ], consumables: [ Sigil ] }";
+
+    // Flush, (35 + 2 + 5 + 7 + 9 + 10) x 4
+    assert_eq!(score(yaml), 272.0);
+}
+
+// Scaling, history and round-context jokers
+
+#[test]
+fn green_joker_scales_from_its_state() {
+    let yaml = "
+        cards_played: [ K♥ ]
+        jokers: [ Green Joker ]
+        joker_states: { jokers: { Green Joker: { mult: 3 } } }
+    ";
+
+    // Gains 1 Mult for the hand, (5 + 10) x (1 + 4)
+    assert_eq!(score(yaml), 75.0);
+}
+
+#[test]
+fn supernova_adds_the_times_the_hand_was_played() {
+    let yaml = "
+        cards_played: [ K♣, K♥ ]
+        jokers: [ Supernova ]
+        run_history: { play_counts: { Pair: 3 } }
+    ";
+
+    // Pair played 4 times with this one, (10 + 10 + 10) x (2 + 4)
+    assert_eq!(score(yaml), 180.0);
+}
+
+#[test]
+fn card_sharp_multiplies_a_hand_already_played_this_round() {
+    let yaml = "
+        cards_played: [ K♣, K♥ ]
+        jokers: [ Card Sharp ]
+        history: { hands_played_this_round: [ Pair ] }
+    ";
+
+    // (10 + 10 + 10) x 2 x 3
+    assert_eq!(score(yaml), 180.0);
+}
+
+#[test]
+fn loyalty_card_multiplies_every_sixth_hand() {
+    let yaml = "
+        cards_played: [ K♥ ]
+        jokers: [ Loyalty Card ]
+        run_history: { play_counts: { Pair: 5 } }
+    ";
+
+    // (5 + 10) x 4
+    assert_eq!(score(yaml), 60.0);
+}
+
+#[test]
+fn banner_adds_chips_for_each_discard_left() {
+    let yaml = "{ cards_played: [ K♥ ], jokers: [ Banner ], context: { discards_left: 2 } }";
+
+    // (5 + 10 + 60) x 1
+    assert_eq!(score(yaml), 75.0);
+}
+
+#[test]
+fn mystic_summit_adds_mult_without_discards_left() {
+    let yaml = "
+        cards_played: [ K♥ ]
+        jokers: [ Mystic Summit ]
+        context: { discards_left: 0 }
+    ";
+
+    // (5 + 10) x (1 + 15)
+    assert_eq!(score(yaml), 240.0);
+}
+
+#[test]
+fn half_joker_adds_mult_for_small_hands() {
+    // (5 + 10) x (1 + 20)
+    assert_eq!(score("{ cards_played: [ K♥ ], jokers: [ Half Joker ] }"), 315.0);
+}
+
+#[test]
+fn acrobat_multiplies_the_final_hand() {
+    let yaml = "{ cards_played: [ K♥ ], jokers: [ Acrobat ], context: { hands_left: 1 } }";
+
+    // (5 + 10) x 3
+    assert_eq!(score(yaml), 45.0);
+}
+
+#[test]
+fn dusk_retriggers_the_final_hand() {
+    let yaml = "{ cards_played: [ K♥ ], jokers: [ Dusk ], context: { hands_left: 1 } }";
+
+    // 5 + 10 + 10
+    assert_eq!(score(yaml), 25.0);
+}
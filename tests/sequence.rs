@@ -0,0 +1,75 @@
+use ortalab::{ HandSequence, ScoreOptions, SequenceReport, score_sequence };
+
+fn score(yaml: &str) -> SequenceReport {
+    let sequence: HandSequence = serde_yaml::from_str(yaml).unwrap();
+    score_sequence(&sequence, &ScoreOptions::default())
+}
+
+#[test]
+fn hiker_chips_stay_with_the_card_which_scored() {
+    let report = score(
+        "
+        jokers: [ Hiker ]
+        hands:
+          - cards_played: [ K♥ ]
+          - cards_played: [ 2♣ ]
+        "
+    );
+
+    assert_eq!(report.hands[0].score, 15.0);
+    // 5 for High Card and 2 for the 2♣, the K♥ is not played again
+    assert_eq!(report.hands[1].score, 7.0);
+}
+
+#[test]
+fn hiker_chips_count_every_retrigger() {
+    let report = score(
+        "
+        jokers: [ Hiker ]
+        hands:
+          - cards_played: [ 2♠ Red Seal ]
+          - cards_played: [ 2♠ Red Seal ]
+        "
+    );
+
+    // 5 + 2 + 2 + 5 gained on the first trigger
+    assert_eq!(report.hands[0].score, 14.0);
+    // 5 + (2 + 10) + (2 + 15)
+    assert_eq!(report.hands[1].score, 34.0);
+}
+
+#[test]
+fn wee_joker_counts_every_retrigger() {
+    let report = score(
+        "
+        jokers: [ Wee Joker ]
+        hands:
+          - cards_played: [ 2♠ Red Seal ]
+        "
+    );
+
+    // 5 + 2 + 2 + 16 chips from two triggers of the 2♠
+    assert_eq!(report.hands[0].score, 25.0);
+}
+
+#[test]
+fn obelisk_streak_carries_on_until_the_most_played_hand() {
+    let report = score(
+        "
+        jokers: [ Obelisk ]
+        joker_states: { jokers: { Obelisk: { streak: 2 } } }
+        run_history: { play_counts: { Pair: 3 } }
+        hands:
+          - cards_played: [ K♥ ]
+          - cards_played: [ K♥, K♠ ]
+          - cards_played: [ 2♣ ]
+        "
+    );
+
+    // (5 + 10) x 1.6 on the third High Card in a row
+    assert_eq!(report.hands[0].score, 24.0);
+    // Pair is still the most played hand, so the streak ends
+    assert_eq!(report.hands[1].score, 60.0);
+    // (5 + 2) x 1.2, rounded down
+    assert_eq!(report.hands[2].score, 8.0);
+}